@@ -0,0 +1,340 @@
+//! La macro `grammar!`: declara una gramática LL(1) en el código de Rust y
+//! se expande en un parser para ella.
+
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use lexian::build::{Diagnostic, generate_parser};
+use lexian::codegen::{generate_rust_parser};
+
+/// Declara una gramática y se expande en el mismo parser dirigido por tabla
+/// que genera `lexian codegen`: los `enum` `Terminal` y `NonTerminal`, las
+/// producciones, la tabla, `Tree`, `ParseError`, y las funciones `parse` y
+/// `parse_tokens`. Conviene declararla dentro de su propio módulo:
+///
+/// ```text
+/// #[allow(dead_code)]
+/// mod expr {
+///     lexian_macros::grammar! {
+///         E -> T EPrime;
+///         EPrime -> + T EPrime | ' ';
+///         T -> F TPrime;
+///         TPrime -> * F TPrime | ' ';
+///         F -> ( E ) | id;
+///     }
+/// }
+/// ```
+///
+/// Cada producción termina con `;` y sus alternativas se separan con `|`.
+/// EPSILON se escribe `' '` o como una alternativa vacía; también se puede
+/// escribir `ε`, aunque rustc advierte que es una letra griega. Los
+/// símbolos pueden ser identificadores, signos o literales; un símbolo que
+/// no es un token de Rust, como `E'`, se escribe entre comillas: `"E'"`,
+/// igual que un terminal `";"`. Los signos juntos forman un solo símbolo,
+/// así que `->` y `>=` se separan con espacios de los demás; `;` y `|`
+/// siempre separan. Las declaraciones de precedencia se escriben como en el
+/// archivo de entrada, terminadas con `;`: `%left + - ;` (ver
+/// `lexian::transform::expand_precedence`).
+///
+/// Si la gramática no es LL(1), la macro produce un error de compilación en
+/// cada producción involucrada en un conflicto.
+#[proc_macro]
+pub fn grammar(input: TokenStream) -> TokenStream {
+  let symbols = match flatten(input) {
+    Ok(symbols) => symbols,
+    Err(error) => return error,
+  };
+  let productions = match split_productions(&symbols) {
+    Ok(productions) => productions,
+    Err(error) => return error,
+  };
+
+  if productions.is_empty() {
+    return compile_error("la gramática no tiene producciones", Span::call_site());
+  }
+
+  let (lines, spans): (Vec<String>, Vec<Span>) = productions.into_iter().unzip();
+
+  match generate_parser(lines, generate_rust_parser) {
+    Ok(code) => code.parse().unwrap_or_else(|_| {
+      compile_error("el código generado no es válido", Span::call_site())
+    }),
+    Err(diagnostics) => diagnostics
+      .iter()
+      .flat_map(|diagnostic| diagnostic_errors(diagnostic, &spans))
+      .collect(),
+  }
+}
+
+/// Símbolo de la gramática escrito en la macro, con su lugar en el código.
+struct Symbol {
+  text: String,
+  span: Span,
+  /// Si se escribió entre comillas. Así `";"` es un terminal y no separa
+  /// producciones.
+  quoted: bool,
+}
+
+impl Symbol {
+  fn new(text: String, span: Span) -> Self {
+    Symbol { text, span, quoted: false }
+  }
+
+  /// Regresa el separador (`->`, `|` o `;`) si el símbolo es uno.
+  fn separator(&self) -> Option<&str> {
+    match self.text.as_str() {
+      "->" | "|" | ";" if !self.quoted => Some(&self.text),
+      _ => None,
+    }
+  }
+}
+
+/// Convierte los tokens de la macro en símbolos. Los grupos se aplanan en
+/// sus delimitadores y su contenido, y los signos juntos se unen en un solo
+/// símbolo.
+fn flatten(input: TokenStream) -> Result<Vec<Symbol>, TokenStream> {
+  let mut symbols = vec![];
+  let mut punctuation: Option<Symbol> = None;
+
+  for tree in input {
+    // `;` y `|` siempre separan, aunque estén junto a otros signos.
+    if let TokenTree::Punct(punct) = &tree {
+      if punct.as_char() != ';' && punct.as_char() != '|' {
+        let joined = match punctuation.take() {
+          Some(mut previous) => {
+            previous.text.push(punct.as_char());
+            previous
+          },
+          None => Symbol::new(punct.as_char().to_string(), punct.span()),
+        };
+
+        match punct.spacing() {
+          Spacing::Joint => punctuation = Some(joined),
+          Spacing::Alone => symbols.push(joined),
+        }
+        continue;
+      }
+    }
+
+    symbols.extend(punctuation.take());
+
+    match tree {
+      TokenTree::Punct(punct) => {
+        symbols.push(Symbol::new(punct.as_char().to_string(), punct.span()));
+      },
+      TokenTree::Group(group) => {
+        let (open, close) = match group.delimiter() {
+          Delimiter::Parenthesis => ("(", ")"),
+          Delimiter::Bracket => ("[", "]"),
+          Delimiter::Brace => ("{", "}"),
+          Delimiter::None => ("", ""),
+        };
+
+        if !open.is_empty() {
+          symbols.push(Symbol::new(open.to_string(), group.span_open()));
+        }
+        symbols.extend(flatten(group.stream())?);
+        if !close.is_empty() {
+          symbols.push(Symbol::new(close.to_string(), group.span_close()));
+        }
+      },
+      TokenTree::Ident(ident) => {
+        let text = ident.to_string();
+        symbols.push(Symbol::new(text.trim_start_matches("r#").to_string(), ident.span()));
+      },
+      TokenTree::Literal(literal) => {
+        let quoted = literal.to_string().starts_with('"');
+        let text = literal_text(&literal)?;
+        symbols.push(Symbol { text, span: literal.span(), quoted });
+      },
+    }
+  }
+
+  symbols.extend(punctuation);
+  Ok(symbols)
+}
+
+/// Regresa el símbolo escrito como literal: el contenido de una cadena,
+/// EPSILON para `' '`, o el literal tal cual (por ejemplo, un número).
+fn literal_text(literal: &Literal) -> Result<String, TokenStream> {
+  let text = literal.to_string();
+
+  if text == "' '" {
+    return Ok(String::from("ε"));
+  }
+
+  let content = match text.strip_prefix('"').and_then(|rest| rest.strip_suffix('"')) {
+    Some(content) => content,
+    // Un carácter como `'a'` también empezaría con `'`.
+    None if reserved(&text) => return Err(reserved_error(&text, literal.span())),
+    None => return Ok(text),
+  };
+
+  if content.is_empty() || content.contains(|c: char| c.is_whitespace() || c == '\\') {
+    return Err(compile_error(
+      "un símbolo entre comillas no puede estar vacío ni tener espacios o `\\`",
+      literal.span(),
+    ));
+  }
+
+  if reserved(content) {
+    return Err(reserved_error(content, literal.span()));
+  }
+
+  Ok(content.to_string())
+}
+
+/// Regresa si el símbolo está reservado por el formato de las producciones:
+/// los separadores `|` y `->`, EPSILON (`ε`), y `'`, con el que se escribe
+/// EPSILON como `' '`. Un símbolo que empieza con `'` tampoco se puede usar.
+fn reserved(symbol: &str) -> bool {
+  symbol == "|" || symbol == "->" || symbol == "ε" || symbol.starts_with('\'')
+}
+
+/// Regresa el error de un símbolo reservado, en el lugar donde se escribió.
+fn reserved_error(symbol: &str, span: Span) -> TokenStream {
+  compile_error(&format!("`{}` está reservado y no puede ser un símbolo", symbol), span)
+}
+
+/// Separa los símbolos en producciones de la forma `A -> α | β ;` y
+/// declaraciones de precedencia como `%left + - ;`. Regresa cada alternativa
+/// como una línea de producciones (`A -> α`) y cada declaración como una
+/// línea `%left + -`, junto con el lugar del código donde empieza.
+fn split_productions(symbols: &[Symbol]) -> Result<Vec<(String, Span)>, TokenStream> {
+  let mut productions = vec![];
+  let mut symbols = symbols.iter();
+
+  while let Some(left) = symbols.next() {
+    // Declaración de precedencia, por ejemplo `%left + - ;`. La revisa
+    // `generate_parser`.
+    if left.text == "%" && !left.quoted {
+      let mut declaration = String::from("%");
+
+      for symbol in symbols.by_ref() {
+        match symbol.separator() {
+          Some(";") => break,
+          Some(_) => return Err(compile_error("falta `;` al final de la declaración", symbol.span)),
+          None if declaration == "%" => declaration.push_str(&symbol.text),
+          None => declaration.push_str(&format!(" {}", symbol.text)),
+        }
+      }
+
+      productions.push((declaration, left.span));
+      continue;
+    }
+
+    if left.separator().is_some() {
+      return Err(compile_error(
+        &format!("se esperaba un no terminal, se encontró `{}`", left.text),
+        left.span,
+      ));
+    }
+
+    let arrow = match symbols.next() {
+      Some(arrow) if arrow.separator() == Some("->") => arrow,
+      Some(other) => return Err(compile_error("se esperaba `->`", other.span)),
+      None => return Err(compile_error("se esperaba `->`", left.span)),
+    };
+
+    let mut body: Vec<&str> = vec![];
+    let mut span = arrow.span;
+
+    loop {
+      let symbol = symbols.next();
+
+      match symbol.map(|symbol| (symbol.separator(), symbol.text.as_str())) {
+        None | Some((Some(";"), _)) | Some((Some("|"), _)) => {
+          let body = if body.is_empty() {
+            String::from("ε")
+          } else {
+            body.join(" ")
+          };
+          productions.push((format!("{} -> {}", left.text, body), span));
+        },
+        Some((Some(_), _)) => {
+          return Err(compile_error(
+            "falta `;` al final de la producción anterior", symbol.unwrap().span,
+          ));
+        },
+        Some((None, text)) => {
+          if body.is_empty() {
+            span = symbol.unwrap().span;
+          }
+          body.push(text);
+          continue;
+        },
+      }
+
+      match symbol {
+        Some(separator) if separator.separator() == Some("|") => {
+          body = vec![];
+          span = separator.span;
+        },
+        _ => break,
+      }
+    }
+  }
+
+  Ok(productions)
+}
+
+/// Regresa un error de compilación por cada producción del problema, en el
+/// lugar donde se escribió.
+fn diagnostic_errors(diagnostic: &Diagnostic, spans: &[Span]) -> TokenStream {
+  let productions: Vec<String> = diagnostic.productions
+    .iter()
+    .map(|(_, production)| format!("`{}`", production))
+    .collect();
+  let message = match productions.len() {
+    0 | 1 => format!("la gramática no es LL(1): {}", diagnostic.message),
+    _ => format!(
+      "la gramática no es LL(1): {} entre {}", diagnostic.message, productions.join(" y "),
+    ),
+  };
+
+  if diagnostic.productions.is_empty() {
+    return compile_error(&message, Span::call_site());
+  }
+
+  diagnostic.productions
+    .iter()
+    .map(|(line, _)| compile_error(&message, spans[*line]))
+    .collect()
+}
+
+/// Regresa `compile_error! { "mensaje" }` con el lugar dado, para que el
+/// compilador muestre el error ahí.
+fn compile_error(message: &str, span: Span) -> TokenStream {
+  let mut literal = Literal::string(message);
+  literal.set_span(span);
+
+  let mut punct = Punct::new('!', Spacing::Alone);
+  punct.set_span(span);
+
+  let mut group = Group::new(Delimiter::Brace, TokenTree::Literal(literal).into());
+  group.set_span(span);
+
+  vec![
+    TokenTree::Ident(Ident::new("compile_error", span)),
+    TokenTree::Punct(punct),
+    TokenTree::Group(group),
+  ].into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::reserved;
+
+  #[test]
+  fn reserved_symbols() {
+    for symbol in &["|", "->", "ε", "'", "'a", "'a'", "''"] {
+      assert!(reserved(symbol), "{}", symbol);
+    }
+  }
+
+  #[test]
+  fn symbols_that_are_not_reserved() {
+    for symbol in &["E'", "a|b", "-", ">", ";", "id", "1"] {
+      assert!(!reserved(symbol), "{}", symbol);
+    }
+  }
+}
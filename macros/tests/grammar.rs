@@ -0,0 +1,38 @@
+//! Gramáticas declaradas con `grammar!`: la macro se expande en un parser
+//! que acepta y rechaza las mismas cadenas que el analizador.
+
+#[allow(dead_code)]
+mod expr {
+  lexian_macros::grammar! {
+    E -> T EPrime;
+    EPrime -> + T EPrime | ' ';
+    T -> F TPrime;
+    TPrime -> * F TPrime | ' ';
+    F -> ( E ) | id;
+  }
+}
+
+#[allow(dead_code)]
+mod operators {
+  lexian_macros::grammar! {
+    %left + - ;
+    %left * ;
+    %right "^" ;
+    E -> E + E | E - E | E * E | E "^" E | ( E ) | id;
+  }
+}
+
+#[test]
+fn expression_grammar() {
+  assert!(expr::parse("id + id * id").is_ok());
+  assert!(expr::parse("id * ( id + id )").is_ok());
+  assert!(expr::parse("id * * id").is_err());
+  assert!(expr::parse("id + ( id").is_err());
+}
+
+#[test]
+fn precedence_declarations() {
+  assert!(operators::parse("id + id * id ^ id ^ id").is_ok());
+  assert!(operators::parse("( id - id ) * id").is_ok());
+  assert!(operators::parse("id + * id").is_err());
+}
@@ -0,0 +1,279 @@
+use crate::input::{Input, result_as_annotation};
+use super::{LL1Analyzer, Trace};
+
+/// Estilos del reporte. Se incluyen en el mismo archivo para que el reporte
+/// no dependa de archivos externos.
+const STYLE: &str = "
+    body { font-family: sans-serif; margin: 2em; }
+    table { border-collapse: collapse; margin-bottom: 1em; }
+    table, th, td { border: 1px solid black; }
+    th, td { padding: 0.2em 0.6em; text-align: left; }
+    code { font-family: monospace; }
+    details { margin-bottom: 0.5em; }
+    summary { cursor: pointer; }
+    .accepted { color: #1a7f37; }
+    .rejected { color: #cf222e; }
+    .error { color: #cf222e; }
+";
+
+impl<'analyzer> LL1Analyzer<'analyzer> {
+  /// Regresa la tabla de parseo como una tabla de HTML. Las filas y columnas
+  /// siguen el orden de aparición en la gramática.
+  pub fn get_table_as_html(&self) -> String {
+    let mut table_html = String::from("<table><tr><th>Non Terminal</th>");
+
+    let terminals = &self.table.terminals;
+
+    // Escribe cabecera
+    for terminal in terminals {
+      table_html.push_str(&format!("<th>{}</th>", escape_html(terminal)));
+    }
+    table_html.push_str("</tr>");
+
+    // Escribe cuerpo
+    for non_terminal in &self.grammar.non_terminals {
+      table_html.push_str(&format!("<tr><td>{}</td>", escape_html(non_terminal)));
+
+      for terminal in terminals {
+        match self.table.get(non_terminal, terminal) {
+          Some(index) => {
+            table_html.push_str(&format!(
+              "<td>({}) {}</td>",
+              index,
+              escape_html(&self.grammar.production_as_string(index)),
+            ));
+          },
+          None => {
+            table_html.push_str("<td></td>");
+          },
+        }
+      }
+      table_html.push_str("</tr>");
+    }
+
+    table_html.push_str("</table>");
+
+    table_html
+  }
+
+  /// Regresa un reporte completo en HTML: la gramática con sus producciones
+  /// numeradas, los conjuntos de anulables, FIRST y FOLLOW, los conflictos,
+  /// la tabla de parseo y el resultado de cada entrada con sus pasos.
+  pub fn get_report_as_html(&mut self, inputs: &[Input]) -> String {
+    let mut report = format!(
+      "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+      <title>lexian</title>\n<style>{}</style>\n</head>\n<body>\n",
+      STYLE,
+    );
+
+    report.push_str("<h1>Gramática</h1>\n");
+    report.push_str(&self.get_grammar_as_html());
+    report.push_str("<h1>Conjuntos</h1>\n");
+    report.push_str(&self.get_sets_as_html());
+    report.push_str("<h1>Conflictos</h1>\n");
+    report.push_str(&self.get_conflicts_as_html());
+    report.push_str("<h1>Tabla de parseo</h1>\n");
+    report.push_str(&self.get_table_as_html());
+    report.push_str("\n<h1>Entradas</h1>\n");
+
+    for (index, input) in inputs.iter().enumerate() {
+      let trace = self.trace(&input.text);
+      report.push_str(&get_input_as_html(index, input, &trace));
+    }
+
+    report.push_str("</body>\n</html>");
+
+    report
+  }
+
+  /// Regresa las producciones numeradas, los terminales y los no terminales.
+  fn get_grammar_as_html(&self) -> String {
+    let mut html = String::from("<table><tr><th>#</th><th>Producción</th></tr>");
+
+    for index in 0..self.grammar.sides.left.len() {
+      html.push_str(&format!(
+        "<tr><td>{}</td><td><code>{}</code></td></tr>",
+        index,
+        escape_html(&self.grammar.production_as_string(index)),
+      ));
+    }
+    html.push_str("</table>\n");
+
+    html.push_str(&format!(
+      "<p><b>No terminales:</b> <code>{}</code></p>\n",
+      escape_html(&self.grammar.non_terminals.join(" ")),
+    ));
+    html.push_str(&format!(
+      "<p><b>Terminales:</b> <code>{}</code></p>\n",
+      escape_html(&self.grammar.terminals.join(" ")),
+    ));
+
+    html
+  }
+
+  /// Regresa una tabla con los conjuntos de cada no terminal.
+  fn get_sets_as_html(&self) -> String {
+    let nullables = self.grammar.find_nullables();
+    let mut html = String::from(
+      "<table><tr><th>Non Terminal</th><th>Anulable</th>\
+      <th>FIRST</th><th>FOLLOW</th></tr>",
+    );
+
+    for non_terminal in &self.grammar.non_terminals {
+      let first = self.grammar.first_as_symbols(non_terminal);
+      let follow = self.grammar.follows.get(non_terminal)
+        .cloned()
+        .unwrap_or_default();
+
+      html.push_str(&format!(
+        "<tr><td>{}</td><td>{}</td><td><code>{{ {} }}</code></td>\
+        <td><code>{{ {} }}</code></td></tr>",
+        escape_html(non_terminal),
+        if nullables.contains(non_terminal) {"Sí"} else {"No"},
+        escape_html(&first.join(", ")),
+        escape_html(&follow.join(", ")),
+      ));
+    }
+    html.push_str("</table>\n");
+
+    html
+  }
+
+  /// Regresa la lista de conflictos LL(1) de la tabla.
+  fn get_conflicts_as_html(&self) -> String {
+    if self.conflicts.is_empty() {
+      return String::from("<p>La gramática no tiene conflictos LL(1).</p>\n");
+    }
+
+    let mut html = String::from(
+      "<p>La gramática no es LL(1). En cada casilla con conflicto la tabla \
+      usa la última producción, así que los resultados de las entradas pueden \
+      no corresponder al lenguaje.</p>\n<ul>",
+    );
+    for conflict in &self.conflicts {
+      let productions: Vec<String> = conflict.productions
+        .iter()
+        .map(|index| format!(
+          "<code>({}) {}</code>",
+          index,
+          escape_html(&self.grammar.production_as_string(*index)),
+        ))
+        .collect();
+
+      html.push_str(&format!(
+        "<li>M[{}, {}]: {}</li>",
+        escape_html(&conflict.non_terminal),
+        escape_html(&conflict.terminal),
+        productions.join(", "),
+      ));
+    }
+    html.push_str("</ul>\n");
+
+    html
+  }
+}
+
+/// Regresa el resultado de una entrada como una sección que se puede
+/// expandir para ver el error y los pasos del parser.
+fn get_input_as_html(index: usize, input: &Input, trace: &Trace) -> String {
+  let mut html = format!(
+    "<details><summary><b>Input #{}:</b> <code>{}</code> &mdash; \
+    <span class=\"{}\">{}</span>",
+    index + 1,
+    escape_html(&input.text),
+    if trace.accepted {"accepted"} else {"rejected"},
+    if trace.accepted {"Yes"} else {"No"},
+  );
+
+  if let Some(expected) = input.expected {
+    html.push_str(&format!(
+      " (esperado: {}{})",
+      result_as_annotation(expected),
+      if expected == trace.accepted {""} else {", FALLÓ"},
+    ));
+  }
+  html.push_str("</summary>\n");
+
+  if let Some(error) = &trace.error {
+    html.push_str(&format!("<p class=\"error\">{}</p>\n", escape_html(error)));
+  }
+
+  html.push_str("<table><tr><th>Pila</th><th>Entrada</th><th>Acción</th></tr>");
+  for step in &trace.steps {
+    html.push_str(&format!(
+      "<tr><td><code>{}</code></td><td><code>{}</code></td>\
+      <td><code>{}</code></td></tr>",
+      escape_html(&step.stack.join(" ")),
+      escape_html(&step.input.join(" ")),
+      escape_html(&step.action),
+    ));
+  }
+  html.push_str("</table></details>\n");
+
+  html
+}
+
+/// Escapa los caracteres especiales de HTML para que símbolos como `<` se
+/// muestren tal cual.
+fn escape_html(text: &str) -> String {
+  let mut result = String::new();
+
+  for character in text.chars() {
+    match character {
+      '&' => result.push_str("&amp;"),
+      '<' => result.push_str("&lt;"),
+      '>' => result.push_str("&gt;"),
+      '"' => result.push_str("&quot;"),
+      '\'' => result.push_str("&#39;"),
+      c => result.push(c),
+    }
+  }
+
+  result
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::analyzer::LL1Analyzer;
+  use crate::examples;
+  use super::escape_html;
+
+  fn report(number: usize) -> String {
+    let (_, inputs) = examples::read(number);
+    let mut grammar = examples::grammar(number);
+    let mut analyzer = LL1Analyzer::new(&mut grammar);
+    analyzer.build_table();
+    analyzer.get_report_as_html(&inputs)
+  }
+
+  #[test]
+  fn report_of_ll1_grammar() {
+    let html = report(1);
+
+    assert!(html.contains("<tr><td>7</td><td><code>F -&gt; id</code></td></tr>"));
+    assert!(html.contains(
+      "<tr><td>F</td><td>No</td><td><code>{ (, id }</code></td>\
+      <td><code>{ +, *, ), $ }</code></td></tr>",
+    ));
+    assert!(html.contains("La gramática no tiene conflictos LL(1)."));
+    assert!(html.contains("<b>Input #1:</b> <code>id + id</code>"));
+    assert_eq!(html.matches("<details>").count(), 5);
+    assert_eq!(html.matches(", FALLÓ").count(), 0);
+  }
+
+  #[test]
+  fn report_lists_conflicts() {
+    let html = report(4);
+
+    assert!(html.contains("La gramática no es LL(1)."));
+    assert!(html.contains(
+      "<li>M[A, a]: <code>(0) A -&gt; a A</code>, <code>(2) A -&gt; a B</code></li>",
+    ));
+    assert!(!html.contains("no tiene conflictos"));
+  }
+
+  #[test]
+  fn escape_special_characters() {
+    assert_eq!(escape_html("A -> <b> & \"c\" 'd'"), "A -&gt; &lt;b&gt; &amp; &quot;c&quot; &#39;d&#39;");
+  }
+}
@@ -0,0 +1,173 @@
+use super::LL1Analyzer;
+
+impl<'analyzer> LL1Analyzer<'analyzer> {
+  /// Regresa el análisis completo de la gramática en formato JSON:
+  /// terminales, no terminales, producciones numeradas, conjunto de
+  /// anulables, FIRST y FOLLOW de cada no terminal, conflictos LL(1) y la
+  /// tabla de parseo.
+  ///
+  /// EPSILON se representa como `"ε"` en los conjuntos FIRST y como una
+  /// lista vacía en el lado derecho de las producciones.
+  pub fn get_analysis_as_json(&self) -> String {
+    let grammar = &self.grammar;
+    let mut fields = vec![];
+
+    fields.push(format!(
+      "\"start\": {}",
+      match grammar.non_terminals.first() {
+        Some(start) => json_string(start),
+        None => String::from("null"),
+      },
+    ));
+    fields.push(format!("\"terminals\": {}", json_array(&grammar.terminals)));
+    fields.push(format!(
+      "\"non_terminals\": {}", json_array(&grammar.non_terminals),
+    ));
+
+    // Producciones numeradas, en el orden de la gramática.
+    let mut productions = vec![];
+    for (index, left) in grammar.sides.left.iter().enumerate() {
+      let right = grammar.production_body(index);
+
+      productions.push(format!(
+        "{{\"index\": {}, \"left\": {}, \"right\": {}}}",
+        index, json_string(left), json_array(&right),
+      ));
+    }
+    fields.push(format!("\"productions\": [{}]", productions.join(", ")));
+
+    fields.push(format!(
+      "\"nullable\": {}", json_array(&grammar.find_nullables()),
+    ));
+
+    // Conjuntos FIRST y FOLLOW por no terminal.
+    let mut firsts = vec![];
+    let mut follows = vec![];
+    for non_terminal in &grammar.non_terminals {
+      let first = grammar.first_as_symbols(non_terminal);
+      let follow = grammar.follows.get(non_terminal)
+        .cloned()
+        .unwrap_or_default();
+
+      firsts.push(format!("{}: {}", json_string(non_terminal), json_array(&first)));
+      follows.push(format!("{}: {}", json_string(non_terminal), json_array(&follow)));
+    }
+    fields.push(format!("\"first\": {{{}}}", firsts.join(", ")));
+    fields.push(format!("\"follow\": {{{}}}", follows.join(", ")));
+
+    // Conflictos LL(1) encontrados al construir la tabla.
+    let mut conflicts = vec![];
+    for conflict in &self.conflicts {
+      let indexes: Vec<String> = conflict.productions
+        .iter()
+        .map(|index| index.to_string())
+        .collect();
+
+      conflicts.push(format!(
+        "{{\"non_terminal\": {}, \"terminal\": {}, \"productions\": [{}]}}",
+        json_string(&conflict.non_terminal),
+        json_string(&conflict.terminal),
+        indexes.join(", "),
+      ));
+    }
+    fields.push(format!("\"is_ll1\": {}", self.conflicts.is_empty()));
+    fields.push(format!("\"conflicts\": [{}]", conflicts.join(", ")));
+
+    // Tabla de parseo: por cada no terminal, el índice de la producción a
+    // usar con cada terminal.
+    let mut rows = vec![];
+    for non_terminal in &grammar.non_terminals {
+      let mut cells = vec![];
+
+      for (terminal, index) in self.table.row(non_terminal) {
+        cells.push(format!("{}: {}", json_string(terminal), index));
+      }
+
+      rows.push(format!("{}: {{{}}}", json_string(non_terminal), cells.join(", ")));
+    }
+    fields.push(format!("\"table\": {{{}}}", rows.join(", ")));
+
+    format!("{{\n  {}\n}}", fields.join(",\n  "))
+  }
+}
+
+/// Convierte un texto en un string de JSON, escapando los caracteres
+/// especiales.
+fn json_string(text: &str) -> String {
+  let mut result = String::from("\"");
+
+  for character in text.chars() {
+    match character {
+      '"' => result.push_str("\\\""),
+      '\\' => result.push_str("\\\\"),
+      '\n' => result.push_str("\\n"),
+      '\r' => result.push_str("\\r"),
+      '\t' => result.push_str("\\t"),
+      c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+      c => result.push(c),
+    }
+  }
+
+  result.push('"');
+  result
+}
+
+/// Convierte una lista de textos en un arreglo de JSON.
+fn json_array(elements: &[String]) -> String {
+  let elements: Vec<String> = elements
+    .iter()
+    .map(|element| json_string(element))
+    .collect();
+
+  format!("[{}]", elements.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::analyzer::LL1Analyzer;
+  use crate::examples;
+  use super::json_string;
+
+  fn analysis(number: usize) -> String {
+    let mut grammar = examples::grammar(number);
+    let mut analyzer = LL1Analyzer::new(&mut grammar);
+    analyzer.build_table();
+    analyzer.get_analysis_as_json()
+  }
+
+  #[test]
+  fn analysis_of_ll1_grammar() {
+    let expected = r#"{
+  "start": "goal",
+  "terminals": ["(", ")", "a", "b"],
+  "non_terminals": ["goal", "A", "two"],
+  "productions": [{"index": 0, "left": "goal", "right": ["A"]}, {"index": 1, "left": "A", "right": ["(", "A", ")"]}, {"index": 2, "left": "A", "right": ["two"]}, {"index": 3, "left": "two", "right": ["a"]}, {"index": 4, "left": "two", "right": ["b"]}],
+  "nullable": [],
+  "first": {"goal": ["(", "a", "b"], "A": ["(", "a", "b"], "two": ["a", "b"]},
+  "follow": {"goal": ["$"], "A": [")", "$"], "two": [")", "$"]},
+  "is_ll1": true,
+  "conflicts": [],
+  "table": {"goal": {"(": 0, "a": 0, "b": 0}, "A": {"(": 1, "a": 2, "b": 2}, "two": {"a": 3, "b": 4}}
+}"#;
+
+    assert_eq!(analysis(2), expected);
+  }
+
+  #[test]
+  fn analysis_with_conflicts_and_epsilon() {
+    let json = analysis(4);
+
+    assert!(json.contains(r#""nullable": ["D"]"#));
+    assert!(json.contains(r#"{"index": 5, "left": "D", "right": []}"#));
+    assert!(json.contains(r#""D": ["ε"]"#));
+    assert!(json.contains(r#""is_ll1": false"#));
+    assert!(json.contains(r#""conflicts": [{"non_terminal": "A", "terminal": "a", "productions": [0, 2]}]"#));
+  }
+
+  #[test]
+  fn escape_strings() {
+    assert_eq!(json_string("id"), r#""id""#);
+    assert_eq!(json_string(r#"a"b\c"#), r#""a\"b\\c""#);
+    assert_eq!(json_string("\n\t\u{1}"), r#""\n\t\u0001""#);
+  }
+}
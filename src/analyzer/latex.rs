@@ -0,0 +1,184 @@
+use super::LL1Analyzer;
+
+impl<'analyzer> LL1Analyzer<'analyzer> {
+  /// Regresa la gramática, los conjuntos FIRST y FOLLOW y la tabla de parseo
+  /// como entornos `tabular` de LaTeX. Las filas y columnas siguen el orden
+  /// de aparición en la gramática.
+  pub fn get_analysis_as_latex(&self) -> String {
+    let mut latex = self.get_grammar_as_latex();
+    latex.push('\n');
+    latex.push_str(&self.get_sets_as_latex());
+    latex.push('\n');
+    latex.push_str(&self.get_table_as_latex());
+
+    latex
+  }
+
+  /// Regresa las producciones numeradas como un `tabular` de LaTeX.
+  pub fn get_grammar_as_latex(&self) -> String {
+    let mut latex = String::from("\\begin{tabular}{r|l}\n\\hline\n");
+    latex.push_str("\\# & Producción \\\\\n\\hline\n");
+
+    for index in 0..self.grammar.sides.left.len() {
+      latex.push_str(&format!(
+        "{} & {} \\\\\n", index, self.production_as_latex(index),
+      ));
+    }
+    latex.push_str("\\hline\n\\end{tabular}\n");
+
+    latex
+  }
+
+  /// Regresa los conjuntos FIRST y FOLLOW de cada no terminal como un
+  /// `tabular` de LaTeX.
+  pub fn get_sets_as_latex(&self) -> String {
+    let mut latex = String::from("\\begin{tabular}{l|l|l}\n\\hline\n");
+    latex.push_str("Non Terminal & FIRST & FOLLOW \\\\\n\\hline\n");
+
+    for non_terminal in &self.grammar.non_terminals {
+      let first = self.grammar.first_as_symbols(non_terminal);
+      let follow = self.grammar.follows.get(non_terminal)
+        .cloned()
+        .unwrap_or_default();
+
+      latex.push_str(&format!(
+        "{} & {} & {} \\\\\n",
+        latex_symbol(non_terminal),
+        latex_set(&first),
+        latex_set(&follow),
+      ));
+    }
+    latex.push_str("\\hline\n\\end{tabular}\n");
+
+    latex
+  }
+
+  /// Regresa la tabla de parseo como un `tabular` de LaTeX.
+  pub fn get_table_as_latex(&self) -> String {
+    let terminals = &self.table.terminals;
+
+    // Escribe cabecera
+    let mut latex = format!(
+      "\\begin{{tabular}}{{l|{}}}\n\\hline\nNon Terminal",
+      "l".repeat(terminals.len()),
+    );
+    for terminal in terminals {
+      latex.push_str(&format!(" & {}", latex_symbol(terminal)));
+    }
+    latex.push_str(" \\\\\n\\hline\n");
+
+    // Escribe cuerpo
+    for non_terminal in &self.grammar.non_terminals {
+      latex.push_str(&latex_symbol(non_terminal));
+
+      for terminal in terminals {
+        match self.table.get(non_terminal, terminal) {
+          Some(index) => {
+            latex.push_str(&format!(" & {}", self.production_as_latex(index)));
+          },
+          None => {
+            latex.push_str(" &");
+          },
+        }
+      }
+      latex.push_str(" \\\\\n");
+    }
+    latex.push_str("\\hline\n\\end{tabular}\n");
+
+    latex
+  }
+
+  /// Regresa la producción con el índice dado en LaTeX.
+  fn production_as_latex(&self, index: usize) -> String {
+    let body = self.grammar.production_body(index);
+
+    format!(
+      "{} $\\rightarrow$ {}",
+      latex_symbol(&self.grammar.sides.left[index]),
+      if body.is_empty() {
+        String::from("$\\varepsilon$")
+      } else {
+        format!("\\texttt{{{}}}", latex_escape(&body.join(" ")))
+      },
+    )
+  }
+}
+
+/// Muestra un símbolo de la gramática en LaTeX. EPSILON (`ε`) se muestra
+/// como `$\varepsilon$`.
+fn latex_symbol(symbol: &str) -> String {
+  if symbol == "ε" {
+    return String::from("$\\varepsilon$");
+  }
+
+  format!("\\texttt{{{}}}", latex_escape(symbol))
+}
+
+/// Muestra un conjunto de símbolos en LaTeX.
+fn latex_set(elements: &[String]) -> String {
+  let elements: Vec<String> = elements
+    .iter()
+    .map(|element| latex_symbol(element))
+    .collect();
+
+  format!("\\{{ {} \\}}", elements.join(", "))
+}
+
+/// Escapa los caracteres especiales de LaTeX.
+fn latex_escape(text: &str) -> String {
+  let mut result = String::new();
+
+  for character in text.chars() {
+    match character {
+      '\\' => result.push_str("\\textbackslash{}"),
+      '{' => result.push_str("\\{"),
+      '}' => result.push_str("\\}"),
+      '$' => result.push_str("\\$"),
+      '&' => result.push_str("\\&"),
+      '#' => result.push_str("\\#"),
+      '%' => result.push_str("\\%"),
+      '_' => result.push_str("\\_"),
+      '^' => result.push_str("\\^{}"),
+      '~' => result.push_str("\\textasciitilde{}"),
+      '<' => result.push_str("\\textless{}"),
+      '>' => result.push_str("\\textgreater{}"),
+      '|' => result.push_str("\\textbar{}"),
+      c => result.push(c),
+    }
+  }
+
+  result
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::analyzer::LL1Analyzer;
+  use crate::examples;
+  use super::{latex_escape, latex_symbol};
+
+  #[test]
+  fn analysis_of_example() {
+    let mut grammar = examples::grammar(1);
+    let mut analyzer = LL1Analyzer::new(&mut grammar);
+    analyzer.build_table();
+    let latex = analyzer.get_analysis_as_latex();
+
+    assert_eq!(latex.matches("\\begin{tabular}").count(), 3);
+    assert!(latex.contains("2 & \\texttt{EPrime} $\\rightarrow$ $\\varepsilon$ \\\\\n"));
+    assert!(latex.contains(
+      "\\texttt{E} & \\{ \\texttt{(}, \\texttt{id} \\} & \\{ \\texttt{)}, \\texttt{\\$} \\} \\\\\n",
+    ));
+    assert!(latex.contains("\\begin{tabular}{l|llllll}\n"));
+  }
+
+  #[test]
+  fn escape_special_characters() {
+    assert_eq!(latex_escape("a_b & {c} ^ $ % # ~ \\"), "a\\_b \\& \\{c\\} \\^{} \\$ \\% \\# \\textasciitilde{} \\textbackslash{}");
+    assert_eq!(latex_symbol("ε"), "$\\varepsilon$");
+  }
+
+  #[test]
+  fn escape_text_mode_symbols() {
+    assert_eq!(latex_escape("a < b > c | d"), "a \\textless{} b \\textgreater{} c \\textbar{} d");
+  }
+}
@@ -0,0 +1,169 @@
+use super::LL1Analyzer;
+
+impl<'analyzer> LL1Analyzer<'analyzer> {
+  /// Regresa la gramática, los conjuntos FIRST y FOLLOW y la tabla de parseo
+  /// en Markdown (GitHub-flavoured). Las filas y columnas siguen el orden de
+  /// aparición en la gramática.
+  pub fn get_analysis_as_markdown(&self) -> String {
+    let mut markdown = String::from("## Gramática\n\n");
+    markdown.push_str(&self.get_grammar_as_markdown());
+    markdown.push_str("\n## Conjuntos FIRST y FOLLOW\n\n");
+    markdown.push_str(&self.get_sets_as_markdown());
+    markdown.push_str("\n## Tabla de parseo\n\n");
+    markdown.push_str(&self.get_table_as_markdown());
+
+    markdown
+  }
+
+  /// Regresa las producciones numeradas como una tabla de Markdown.
+  pub fn get_grammar_as_markdown(&self) -> String {
+    let mut markdown = String::from("| # | Producción |\n| ---: | --- |\n");
+
+    for index in 0..self.grammar.sides.left.len() {
+      markdown.push_str(&format!(
+        "| {} | {} |\n",
+        index,
+        markdown_code(&self.grammar.production_as_string(index)),
+      ));
+    }
+
+    markdown
+  }
+
+  /// Regresa los conjuntos FIRST y FOLLOW de cada no terminal como una tabla
+  /// de Markdown.
+  pub fn get_sets_as_markdown(&self) -> String {
+    let mut markdown = String::from(
+      "| Non Terminal | FIRST | FOLLOW |\n| --- | --- | --- |\n",
+    );
+
+    for non_terminal in &self.grammar.non_terminals {
+      let first = self.grammar.first_as_symbols(non_terminal);
+      let follow = self.grammar.follows.get(non_terminal)
+        .cloned()
+        .unwrap_or_default();
+
+      markdown.push_str(&format!(
+        "| {} | {} | {} |\n",
+        markdown_code(non_terminal),
+        markdown_set(&first),
+        markdown_set(&follow),
+      ));
+    }
+
+    markdown
+  }
+
+  /// Regresa la tabla de parseo como una tabla de Markdown.
+  pub fn get_table_as_markdown(&self) -> String {
+    let terminals = &self.table.terminals;
+
+    // Escribe cabecera
+    let mut markdown = String::from("| Non Terminal |");
+    for terminal in terminals {
+      markdown.push_str(&format!(" {} |", markdown_code(terminal)));
+    }
+    markdown.push_str("\n| --- |");
+    for _ in terminals {
+      markdown.push_str(" --- |");
+    }
+    markdown.push('\n');
+
+    // Escribe cuerpo
+    for non_terminal in &self.grammar.non_terminals {
+      markdown.push_str(&format!("| {} |", markdown_code(non_terminal)));
+
+      for terminal in terminals {
+        match self.table.get(non_terminal, terminal) {
+          Some(index) => {
+            markdown.push_str(&format!(
+              " ({}) {} |",
+              index,
+              markdown_code(&self.grammar.production_as_string(index)),
+            ));
+          },
+          None => {
+            markdown.push_str("  |");
+          },
+        }
+      }
+      markdown.push('\n');
+    }
+
+    markdown
+  }
+}
+
+/// Muestra un texto como código en Markdown, escapando `|` para que no
+/// rompa las tablas.
+fn markdown_code(text: &str) -> String {
+  let text = text.replace('|', "\\|");
+
+  // Si el texto contiene comillas invertidas se usan dobles como
+  // delimitador.
+  if text.contains('`') {
+    format!("`` {} ``", text)
+  } else {
+    format!("`{}`", text)
+  }
+}
+
+/// Muestra un conjunto de símbolos en Markdown.
+fn markdown_set(elements: &[String]) -> String {
+  let elements: Vec<String> = elements
+    .iter()
+    .map(|element| markdown_code(element))
+    .collect();
+
+  format!("{{ {} }}", elements.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::analyzer::LL1Analyzer;
+  use crate::examples;
+  use super::markdown_code;
+
+  #[test]
+  fn analysis_of_example() {
+    let mut grammar = examples::grammar(2);
+    let mut analyzer = LL1Analyzer::new(&mut grammar);
+    analyzer.build_table();
+
+    let expected = "\
+## Gramática
+
+| # | Producción |
+| ---: | --- |
+| 0 | `goal -> A` |
+| 1 | `A -> ( A )` |
+| 2 | `A -> two` |
+| 3 | `two -> a` |
+| 4 | `two -> b` |
+
+## Conjuntos FIRST y FOLLOW
+
+| Non Terminal | FIRST | FOLLOW |
+| --- | --- | --- |
+| `goal` | { `(`, `a`, `b` } | { `$` } |
+| `A` | { `(`, `a`, `b` } | { `)`, `$` } |
+| `two` | { `a`, `b` } | { `)`, `$` } |
+
+## Tabla de parseo
+
+| Non Terminal | `(` | `)` | `a` | `b` | `$` |
+| --- | --- | --- | --- | --- | --- |
+| `goal` | (0) `goal -> A` |  | (0) `goal -> A` | (0) `goal -> A` |  |
+| `A` | (1) `A -> ( A )` |  | (2) `A -> two` | (2) `A -> two` |  |
+| `two` |  |  | (3) `two -> a` | (4) `two -> b` |  |
+";
+
+    assert_eq!(analyzer.get_analysis_as_markdown(), expected);
+  }
+
+  #[test]
+  fn code_with_pipes_and_backticks() {
+    assert_eq!(markdown_code("a | b"), "`a \\| b`");
+    assert_eq!(markdown_code("`a`"), "`` `a` ``");
+  }
+}
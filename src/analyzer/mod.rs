@@ -1,7 +1,46 @@
-use std::collections::{HashMap};
+mod html;
+mod json;
+mod latex;
+mod markdown;
+mod table;
+
 use super::grammar::{Grammar};
 
-pub type TableTerminals = HashMap<String, usize>;
+pub use table::ParseTable;
+
+/// Conflicto LL(1): más de una producción en la misma casilla de la tabla.
+pub struct Conflict {
+  /// No terminal de la fila de la casilla.
+  pub non_terminal: String,
+  /// Terminal de la columna de la casilla.
+  pub terminal: String,
+  /// Índices de las producciones que compiten por la casilla.
+  pub productions: Vec<usize>,
+}
+
+/// Paso del parser predictivo al evaluar una cadena.
+pub struct Step {
+  /// Contenido de la pila antes del paso (el tope es el último elemento).
+  pub stack: Vec<String>,
+  /// Entrada que falta por consumir antes del paso.
+  pub input: Vec<String>,
+  /// Acción realizada: la producción usada, `match`, `accept` o `error`.
+  pub action: String,
+}
+
+/// Resultado detallado de evaluar una cadena.
+pub struct Trace {
+  /// `true` si la cadena fue aceptada.
+  pub accepted: bool,
+  /// Pasos realizados por el parser.
+  pub steps: Vec<Step>,
+  /// Mensaje de error si la cadena no fue aceptada.
+  pub error: Option<String>,
+  /// Posición (empezando en 1) del elemento de la entrada donde se encontró
+  /// el error. El final de la entrada (`$`) tiene la posición siguiente al
+  /// último elemento.
+  pub error_position: Option<usize>,
+}
 
 /// Estructura de un parser genérico.
 struct Parser {
@@ -12,7 +51,9 @@ struct Parser {
 /// Estructura que representa un analizador LL1.
 pub struct LL1Analyzer<'analyzer> {
   /// Tabla de parseo predictivo del analizador.
-  pub table: HashMap<String, TableTerminals>,
+  pub table: ParseTable,
+  /// Conflictos encontrados al construir la tabla.
+  pub conflicts: Vec<Conflict>,
   /// Parser predictivo no recursivo.
   parser: Parser,
   /// Built grammar.
@@ -23,25 +64,15 @@ impl<'analyzer> LL1Analyzer<'analyzer> {
   /// Crea la estructura inicial del LL1Analyzer.
   pub fn new(
     grammar: &'analyzer mut Grammar
-  ) -> LL1Analyzer {
-    let mut analyzer = LL1Analyzer {
-      table: HashMap::new(),
+  ) -> LL1Analyzer<'analyzer> {
+    LL1Analyzer {
+      table: ParseTable::new(&grammar.non_terminals, &grammar.terminals),
+      conflicts: vec![],
       parser: Parser {
         stack: vec![],
         input: vec![],
       },
       grammar,
-    };
-
-    analyzer.build_table_struct();
-
-    analyzer
-  }
-
-  /// Crea la estructura inicial de la tabla del parser.
-  fn build_table_struct(&mut self) {
-    for non_terminal in self.grammar.non_terminals.to_owned() {
-      self.table.insert(non_terminal, HashMap::new());
     }
   }
 
@@ -50,6 +81,10 @@ impl<'analyzer> LL1Analyzer<'analyzer> {
   /// 2. If EPSILON is in FIRST(a), then for each terminal b in FOLLOW(A),
   ///    add A -> a to M[A, b]. If EPSILON is in FIRST(a) and $ in FOLLOW(A),
   ///    add A -> a to M[A, $] as well.
+  ///
+  /// If a cell already holds another production, the conflict is recorded in
+  /// `conflicts` and the last production wins. Conflicts are sorted in the
+  /// same order as the rows and columns of the table.
   pub fn build_table(&mut self) {
     let left = self.grammar.sides.left.to_owned();
     for (index, non_terminal) in left.iter().enumerate() {
@@ -58,14 +93,12 @@ impl<'analyzer> LL1Analyzer<'analyzer> {
 
       // Primer regla
       // Añade las reglas en sus respetivas casillas
-      for terminal in first.to_owned() {
+      for terminal in first.iter().cloned() {
         if terminal == "' '" {
           continue;
         }
 
-        let mut row_to_insert = self.table.get(non_terminal).unwrap().clone();
-        row_to_insert.insert(terminal, index);
-        self.table.insert(String::from(non_terminal), row_to_insert);
+        self.insert_in_table(non_terminal, &terminal, index);
       }
 
       // Segunda regla
@@ -74,66 +107,56 @@ impl<'analyzer> LL1Analyzer<'analyzer> {
       }
 
       // Añade las reglas a M[A, b], donde b pertenece a FOLLOW(A)
-      for terminal in self.grammar.follows.get(non_terminal).unwrap() {
-        let mut row_to_insert = self.table.get(non_terminal).unwrap().clone();
-        row_to_insert.insert(String::from(terminal), index);
-        self.table.insert(String::from(non_terminal), row_to_insert);
+      for terminal in self.grammar.follows.get(non_terminal).unwrap().clone() {
+        self.insert_in_table(non_terminal, &terminal, index);
       }
     }
+
+    let non_terminals = &self.table.non_terminals;
+    let terminals = &self.table.terminals;
+    self.conflicts.sort_by_key(|conflict| (
+      non_terminals.iter().position(|el| el == &conflict.non_terminal),
+      terminals.iter().position(|el| el == &conflict.terminal),
+    ));
   }
 
-  pub fn get_table_as_html(&self) -> String {
-    let mut table_html = String::from(
-      "
-      <html>
-        <style>
-          table, th, td {
-            border:1px solid black;
+  /// Añade la producción `index` a la casilla M[non_terminal, terminal],
+  /// registrando un conflicto si la casilla ya estaba ocupada.
+  fn insert_in_table(&mut self, non_terminal: &str, terminal: &str, index: usize) {
+    if let Some(previous) = self.table.insert(non_terminal, terminal, index) {
+      if previous == index {
+        return;
+      }
+
+      // Si ya existe un conflicto en la casilla, se agrega la producción.
+      for conflict in self.conflicts.iter_mut() {
+        if conflict.non_terminal == non_terminal && conflict.terminal == terminal {
+          if !conflict.productions.contains(&index) {
+            conflict.productions.push(index);
           }
-        </style>
-        <body>
-          <table>
-            <tr>
-              <th>Non Terminal</th>"
-    );
-
-    let mut terminals = self.grammar.terminals.to_owned();
-    terminals.push(String::from("$"));
-
-    // Escribe cabecera
-    for terminal in terminals.to_owned() {
-      table_html.push_str(&format!("<th>{}</th>", terminal));
-    }
-    table_html.push_str("</tr>");
-
-    // Escribe cuerpo
-    for (non_terminal, row_to_insert) in self.table.iter() {
-      table_html.push_str(&format!("<tr><td>{}</td>", non_terminal));
-      for terminal in terminals.to_owned() {
-        match row_to_insert.get(&terminal) {
-          Some(res) => {
-            let prod = self.grammar.productions.get(res).unwrap();
-            table_html.push_str(
-              &format!("<td>{} -> {}</td>", non_terminal, prod.join(" ")),
-            );
-          },
-          None => {
-            table_html.push_str("<td></td>");
-          },
+          return;
         }
       }
-      table_html.push_str("</tr>");
-    }
-
-    table_html.push_str("</table></html></body>");
 
-    table_html
+      self.conflicts.push(Conflict {
+        non_terminal: String::from(non_terminal),
+        terminal: String::from(terminal),
+        productions: vec![previous, index],
+      });
+    }
   }
 
   /// Evalúa una cadena de texto con el analizador LL(1).
   /// Regresa `true` si es aceptada la cadena.
   /// Regresa `false` si no fue aceptada.
-  pub fn eval(&mut self, input: &String) -> bool {
+  pub fn eval(&mut self, input: &str) -> bool {
+    self.trace(input).accepted
+  }
+
+  /// Evalúa una cadena de texto con el analizador LL(1) y regresa cada uno
+  /// de los pasos del parser, junto con el mensaje de error si la cadena no
+  /// fue aceptada.
+  pub fn trace(&mut self, input: &str) -> Trace {
     // Reinicia el parser
     self.parser.input = self.split_input(input);
     self.parser.input.push(String::from("$"));
@@ -142,67 +165,113 @@ impl<'analyzer> LL1Analyzer<'analyzer> {
       String::from(self.grammar.non_terminals.first().unwrap()),
     ];
 
-    let mut last_stack: &String;
-    let mut first_input: &String;
-    let mut production;
-    loop {
-      if self.parser.stack.len() == 0 || self.parser.input.len() == 0 {
-        return false;
-      }
+    let mut trace = Trace {
+      accepted: false,
+      steps: vec![],
+      error: None,
+      error_position: None,
+    };
+    // Posición (empezando en 1) del siguiente elemento de la entrada.
+    let mut position = 1;
 
-      first_input = match self.parser.input.first() {
-        Some(last) => last,
-        None => {return false},
+    loop {
+      let (last_stack, first_input) = match (
+        self.parser.stack.last(), self.parser.input.first(),
+      ) {
+        (Some(last_stack), Some(first_input)) => {
+          (last_stack.to_owned(), first_input.to_owned())
+        },
+        _ => {
+          trace.error = Some(String::from(
+            "La pila o la entrada terminaron antes de tiempo",
+          ));
+          trace.error_position = Some(position);
+          return trace;
+        },
       };
-      let temp_stack = self.parser.stack.to_owned();
-      last_stack = match temp_stack.last() {
-        Some(last) => last,
-        None => {return false},
+      let mut step = Step {
+        stack: self.parser.stack.to_owned(),
+        input: self.parser.input.to_owned(),
+        action: String::new(),
       };
 
       // Condición de aceptación de cadena
       if first_input == last_stack && first_input == "$" {
-        return true;
+        step.action = String::from("accept");
+        trace.steps.push(step);
+        trace.accepted = true;
+        return trace;
       }
 
-      if self.grammar.non_terminals.contains(last_stack) {
-        production = self.grammar.productions.get(
-          match self.table.get(last_stack).unwrap().get(first_input) {
-            Some(index) => index,
-            None => {return false},  
+      if self.grammar.non_terminals.contains(&last_stack) {
+        let index = match self.table.get(&last_stack, &first_input) {
+          Some(index) => index,
+          None => {
+            let expected = self.expected_terminals(&last_stack);
+            step.action = String::from("error");
+            trace.steps.push(step);
+            trace.error = Some(format!(
+              "Posición {}: se encontró `{}` pero se esperaba uno de: {}",
+              position, first_input, expected.join(" "),
+            ));
+            trace.error_position = Some(position);
+            return trace;
           },
-        ).unwrap();
+        };
+        let production = self.grammar.productions.get(&index).unwrap();
         self.parser.stack.pop();
 
-        for el in production.into_iter().rev() {
+        for el in production.iter().rev() {
           if el == "'" {
             continue;
           } else {
             self.parser.stack.push(String::from(el));
           }
         };
-      }
 
-      if self.grammar.terminals.contains(last_stack) || last_stack == "$" {
+        step.action = self.grammar.production_as_string(index);
+        trace.steps.push(step);
+      } else if self.grammar.terminals.contains(&last_stack) || last_stack == "$" {
         if first_input == last_stack {
           // eliminamos el último elemento del stack
           self.parser.stack.pop();
           self.parser.input = self.parser.input[1..].to_vec();
+          position += 1;
+
+          step.action = format!("match {}", first_input);
+          trace.steps.push(step);
         } else {
-          return false;
+          step.action = String::from("error");
+          trace.steps.push(step);
+          trace.error = Some(format!(
+            "Posición {}: se encontró `{}` pero se esperaba `{}`",
+            position, first_input, last_stack,
+          ));
+          trace.error_position = Some(position);
+          return trace;
         }
+      } else {
+        step.action = String::from("error");
+        trace.steps.push(step);
+        trace.error = Some(format!("Símbolo desconocido en la pila: `{}`", last_stack));
+        trace.error_position = Some(position);
+        return trace;
       }
     }
   }
 
-  fn split_input(&self, input: &String) -> Vec<String> {
-    let mut result = vec![];
-    let splitted: Vec<&str> = input.split(" ").collect();
-
-    for el in splitted {
-      result.push(String::from(el));
-    }
+  /// Regresa los terminales con una casilla ocupada en la fila del no
+  /// terminal, en el orden de la gramática.
+  fn expected_terminals(&self, non_terminal: &str) -> Vec<String> {
+    self.table.row(non_terminal)
+      .into_iter()
+      .map(|(terminal, _)| String::from(terminal))
+      .collect()
+  }
 
-    result
+  /// Separa la cadena de entrada en sus elementos. Los espacios repetidos
+  /// se ignoran, por lo que una cadena vacía no tiene elementos.
+  fn split_input(&self, input: &str) -> Vec<String> {
+    input.split_whitespace().map(String::from).collect()
   }
 }
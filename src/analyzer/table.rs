@@ -0,0 +1,124 @@
+/// Tabla de parseo predictivo. Las filas son los no terminales y las
+/// columnas los terminales, ambos en el orden de aparición en la gramática,
+/// de manera que recorrer la tabla siempre produce el mismo resultado.
+#[derive(Clone)]
+pub struct ParseTable {
+  /// No terminales de las filas, en orden de aparición.
+  pub non_terminals: Vec<String>,
+  /// Terminales de las columnas, en orden de aparición, seguidos de `$`.
+  pub terminals: Vec<String>,
+  /// Índice de la producción de cada casilla, si existe.
+  cells: Vec<Vec<Option<usize>>>,
+}
+
+impl ParseTable {
+  /// Crea una tabla vacía con las filas y columnas dadas. La columna `$` se
+  /// agrega al final.
+  pub fn new(non_terminals: &[String], terminals: &[String]) -> Self {
+    let mut columns = terminals.to_owned();
+    columns.push(String::from("$"));
+
+    ParseTable {
+      non_terminals: non_terminals.to_owned(),
+      cells: vec![vec![None; columns.len()]; non_terminals.len()],
+      terminals: columns,
+    }
+  }
+
+  /// Regresa el índice de la producción en la casilla M[non_terminal, terminal].
+  pub fn get(&self, non_terminal: &str, terminal: &str) -> Option<usize> {
+    let row = self.row_index(non_terminal)?;
+    let column = self.column_index(terminal)?;
+
+    self.cells[row][column]
+  }
+
+  /// Guarda el índice de la producción en la casilla M[non_terminal, terminal]
+  /// y regresa el índice que estaba antes en la casilla, si existía.
+  pub fn insert(
+    &mut self, non_terminal: &str, terminal: &str, index: usize,
+  ) -> Option<usize> {
+    let row = self.row_index(non_terminal).unwrap();
+    let column = self.column_index(terminal).unwrap();
+
+    self.cells[row][column].replace(index)
+  }
+
+  /// Regresa las casillas ocupadas de la fila de un no terminal, en el orden
+  /// de las columnas.
+  pub fn row(&self, non_terminal: &str) -> Vec<(&String, usize)> {
+    let row = match self.row_index(non_terminal) {
+      Some(row) => row,
+      None => return vec![],
+    };
+
+    self.terminals
+      .iter()
+      .zip(self.cells[row].iter())
+      .filter_map(|(terminal, cell)| cell.map(|index| (terminal, index)))
+      .collect()
+  }
+
+  fn row_index(&self, non_terminal: &str) -> Option<usize> {
+    self.non_terminals.iter().position(|el| el == non_terminal)
+  }
+
+  fn column_index(&self, terminal: &str) -> Option<usize> {
+    self.terminals.iter().position(|el| el == terminal)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::analyzer::LL1Analyzer;
+  use crate::examples;
+  use super::ParseTable;
+
+  fn strings(symbols: &[&str]) -> Vec<String> {
+    symbols.iter().map(|symbol| symbol.to_string()).collect()
+  }
+
+  #[test]
+  fn insert_and_get_cells() {
+    let mut table = ParseTable::new(&strings(&["S", "A"]), &strings(&["b", "a"]));
+
+    assert_eq!(table.terminals, strings(&["b", "a", "$"]));
+    assert_eq!(table.insert("A", "$", 2), None);
+    assert_eq!(table.insert("A", "$", 3), Some(2));
+    assert_eq!(table.get("A", "$"), Some(3));
+    assert_eq!(table.get("S", "a"), None);
+    assert_eq!(table.get("X", "a"), None);
+  }
+
+  #[test]
+  fn rows_follow_the_column_order() {
+    let mut table = ParseTable::new(&strings(&["S"]), &strings(&["b", "a"]));
+    table.insert("S", "$", 1);
+    table.insert("S", "b", 0);
+
+    let row: Vec<(&str, usize)> = table
+      .row("S")
+      .into_iter()
+      .map(|(terminal, index)| (terminal.as_str(), index))
+      .collect();
+    assert_eq!(row, vec![("b", 0), ("$", 1)]);
+    assert!(table.row("X").is_empty());
+  }
+
+  #[test]
+  fn table_of_example_in_grammar_order() {
+    let mut grammar = examples::grammar(1);
+    let mut analyzer = LL1Analyzer::new(&mut grammar);
+    analyzer.build_table();
+
+    assert_eq!(analyzer.table.non_terminals, strings(&["E", "EPrime", "T", "TPrime", "F"]));
+    assert_eq!(analyzer.table.terminals, strings(&["+", "*", "(", ")", "id", "$"]));
+
+    let row: Vec<(&str, usize)> = analyzer.table
+      .row("TPrime")
+      .into_iter()
+      .map(|(terminal, index)| (terminal.as_str(), index))
+      .collect();
+    assert_eq!(row, vec![("+", 5), ("*", 4), (")", 5), ("$", 5)]);
+  }
+}
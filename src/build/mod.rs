@@ -0,0 +1,302 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use crate::analyzer::{LL1Analyzer};
+use crate::codegen::{generate_rust_parser};
+use crate::grammar::{Grammar};
+use crate::input::{FIRST_PRODUCTION_LINE};
+use crate::linter::{LintKind, lint_grammar};
+use crate::transform::{expand_precedence, grammar_with_precedence};
+
+/// Función que genera el código de un parser de Rust, como
+/// `generate_rust_parser` o `generate_rust_descent_parser`.
+pub type Generate = fn(&LL1Analyzer) -> Result<String, String>;
+
+/// Genera un parser de Rust dirigido por tabla para la gramática del archivo
+/// y lo escribe en `OUT_DIR` con el nombre del archivo y extensión `.rs`.
+/// Se llama desde `build.rs`, por ejemplo
+/// `lexian::build::compile_grammar("grammars/expr.grammar")`, y el parser se
+/// incluye con `include!(concat!(env!("OUT_DIR"), "/expr.rs"))`.
+///
+/// Si el archivo no se puede leer o la gramática no es LL(1), imprime los
+/// errores y termina el proceso, lo que detiene la compilación.
+pub fn compile_grammar(path: impl AsRef<Path>) {
+  compile_grammar_with(path, generate_rust_parser);
+}
+
+/// Lo mismo que `compile_grammar`, pero el código lo genera la función
+/// dada, por ejemplo `generate_rust_descent_parser`.
+pub fn compile_grammar_with(path: impl AsRef<Path>, generate: Generate) {
+  let path = path.as_ref();
+  println!("cargo:rerun-if-changed={}", path.display());
+
+  let out_dir = match env::var_os("OUT_DIR") {
+    Some(out_dir) => PathBuf::from(out_dir),
+    None => {
+      eprintln!("error: OUT_DIR no está definido: compile_grammar se llama desde build.rs");
+      process::exit(1);
+    },
+  };
+
+  if let Err(e) = write_parser(path, &out_dir, generate) {
+    for line in e.lines() {
+      eprintln!("error: {}", line);
+    }
+    process::exit(1);
+  }
+}
+
+/// Lee la gramática del archivo, verifica que sea LL(1) y escribe el código
+/// generado en el directorio dado. Regresa la ruta del archivo escrito.
+///
+/// Los errores incluyen el archivo y la línea de las producciones
+/// involucradas.
+pub fn write_parser(path: &Path, out_dir: &Path, generate: Generate) -> Result<PathBuf, String> {
+  let name = path.display();
+  let text = fs::read_to_string(path).map_err(|e| {
+    format!("No se pudo leer el archivo {}: {}", name, e)
+  })?;
+  let (productions, lines) = parse_grammar_file(&text).map_err(|(line, message)| {
+    format!("{}:{}: {}", name, line, message)
+  })?;
+
+  if productions.iter().all(|line| line.trim_start().starts_with('%')) {
+    return Err(format!("{}: la gramática no tiene producciones", name));
+  }
+
+  let code = generate_parser(productions, generate).map_err(|diagnostics| {
+    let mut message = format!("{}: la gramática no es LL(1)", name);
+
+    for diagnostic in diagnostics {
+      if diagnostic.productions.is_empty() {
+        message.push_str(&format!("\n{}: {}", name, diagnostic.message));
+        continue;
+      }
+
+      message.push_str(&format!("\n{}: {}:", name, diagnostic.message));
+      for (line, production) in &diagnostic.productions {
+        message.push_str(&format!("\n{}:{}:   {}", name, lines[*line], production));
+      }
+    }
+
+    message
+  })?;
+
+  let stem = path.file_stem().unwrap_or(path.as_os_str());
+  let output = out_dir.join(stem).with_extension("rs");
+
+  fs::write(&output, code).map_err(|e| {
+    format!("No se pudo escribir al archivo {}: {}", output.display(), e)
+  })?;
+
+  Ok(output)
+}
+
+/// Problema que impide generar el parser.
+pub struct Diagnostic {
+  /// Descripción del problema, por ejemplo `conflicto en M[E, id]`.
+  pub message: String,
+  /// Producciones involucradas: el índice de su línea entre las líneas de
+  /// producciones y la producción.
+  pub productions: Vec<(usize, String)>,
+}
+
+/// Analiza la gramática formada por las líneas de producciones y genera el
+/// código del parser con la función dada. Las líneas pueden tener
+/// declaraciones de precedencia (`%left`, `%right` y `%nonassoc`), con las
+/// que la gramática se expande como en `expand_precedence`.
+///
+/// Regresa los problemas encontrados si las declaraciones no son válidas,
+/// si la gramática tiene ciclos A =>+ A (con los que no se pueden calcular
+/// los conjuntos FIRST) o conflictos en la tabla de parseo. Las producciones
+/// de la gramática expandida no tienen una línea, así que en sus conflictos
+/// van en el mensaje.
+pub fn generate_parser(productions: Vec<String>, generate: Generate) -> Result<String, Vec<Diagnostic>> {
+  let diagnostic = |message| vec![Diagnostic { message, productions: vec![] }];
+  let (precedences, grammar) = grammar_with_precedence(&productions).map_err(diagnostic)?;
+  let production = |grammar: &Grammar, index: usize| {
+    (grammar.sides.lines[index], grammar.production_as_string(index))
+  };
+
+  let cycles: Vec<Diagnostic> = lint_grammar(&grammar)
+    .into_iter()
+    .filter(|lint| matches!(lint.kind, LintKind::Cycle))
+    .map(|lint| {
+      let line = lint.line - FIRST_PRODUCTION_LINE;
+      let index = grammar.sides.lines.iter().position(|el| *el == line).unwrap();
+
+      Diagnostic { message: lint.message, productions: vec![production(&grammar, index)] }
+    })
+    .collect();
+
+  if !cycles.is_empty() {
+    return Err(cycles);
+  }
+
+  let expanded = !precedences.is_empty();
+  let mut grammar = if expanded {
+    expand_precedence(&grammar, &precedences).map_err(diagnostic)?
+  } else {
+    grammar
+  };
+
+  grammar.find_sets();
+
+  let mut analyzer = LL1Analyzer::new(&mut grammar);
+  analyzer.build_table();
+
+  if !analyzer.conflicts.is_empty() {
+    return Err(
+      analyzer.conflicts
+        .iter()
+        .map(|conflict| {
+          let message = format!("conflicto en M[{}, {}]", conflict.non_terminal, conflict.terminal);
+          let productions = conflict.productions
+            .iter()
+            .map(|index| production(analyzer.grammar, *index));
+
+          if expanded {
+            let productions: Vec<String> = productions.map(|(_, production)| production).collect();
+            Diagnostic {
+              message: format!("{} entre {}", message, productions.join(" y ")),
+              productions: vec![],
+            }
+          } else {
+            Diagnostic { message, productions: productions.collect() }
+          }
+        })
+        .collect()
+    );
+  }
+
+  generate(&analyzer).map_err(diagnostic)
+}
+
+/// Lee las producciones de un archivo de gramática: una por línea, con el
+/// mismo formato que en el archivo de entrada. Las líneas vacías se ignoran.
+/// Si el archivo empieza con la línea `N M` de un archivo de entrada, se
+/// leen sus N producciones y se ignoran las entradas. Las declaraciones de
+/// precedencia, que empiezan con `%`, se leen como las producciones y las
+/// revisa `generate_parser`.
+///
+/// Regresa las producciones junto con su número de línea, o la línea y el
+/// mensaje del primer error.
+fn parse_grammar_file(text: &str) -> Result<(Vec<String>, Vec<usize>), (usize, String)> {
+  let mut lines = text
+    .lines()
+    .enumerate()
+    .map(|(index, line)| (index + 1, line))
+    .filter(|(_, line)| !line.trim().is_empty())
+    .peekable();
+  let mut limit = usize::MAX;
+
+  if let Some((_, first)) = lines.peek() {
+    let counts: Vec<Option<usize>> = first.split_whitespace().map(|el| el.parse().ok()).collect();
+
+    if let [Some(productions), Some(_)] = counts.as_slice() {
+      limit = *productions;
+      lines.next();
+    }
+  }
+
+  let mut productions = vec![];
+  let mut numbers = vec![];
+
+  for (number, line) in lines.take(limit) {
+    if line.trim_start().starts_with('%') {
+      productions.push(line.to_owned());
+      numbers.push(number);
+      continue;
+    }
+
+    match line.trim().strip_prefix('|') {
+      Some(_) if productions.is_empty() => {
+        return Err((number, String::from("`|` sin una producción anterior")));
+      },
+      Some(_) => {},
+      None => {
+        let valid = match line.split_once(" -> ") {
+          Some((left, _)) => {
+            let left = left.trim();
+            !left.is_empty() && !left.contains(char::is_whitespace)
+          },
+          None => false,
+        };
+
+        if !valid {
+          return Err((
+            number, format!("se esperaba una producción `A -> α`, se encontró `{}`", line.trim()),
+          ));
+        }
+      },
+    }
+
+    productions.push(line.to_owned());
+    numbers.push(number);
+  }
+
+  Ok((productions, numbers))
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::codegen::generate_rust_parser;
+  use super::{generate_parser, parse_grammar_file};
+
+  fn lines(text: &[&str]) -> Vec<String> {
+    text.iter().map(|line| line.to_string()).collect()
+  }
+
+  #[test]
+  fn grammar_file_with_counts_and_continuations() {
+    let text = "3 1\nS -> a A\n\n  | b\nA -> c\nc # accept\n";
+    let (productions, numbers) = parse_grammar_file(text).unwrap();
+
+    assert_eq!(productions, lines(&["S -> a A", "  | b", "A -> c"]));
+    assert_eq!(numbers, vec![2, 4, 5]);
+  }
+
+  #[test]
+  fn grammar_file_with_precedence_declarations() {
+    let text = "%left + -\n%left *\nE -> E + E | E - E | E * E | id\n";
+    let (productions, numbers) = parse_grammar_file(text).unwrap();
+
+    assert_eq!(productions[0], "%left + -");
+    assert_eq!(numbers, vec![1, 2, 3]);
+    assert!(generate_parser(productions, generate_rust_parser).is_ok());
+  }
+
+  #[test]
+  fn grammar_file_errors() {
+    assert_eq!(parse_grammar_file("| a\n").unwrap_err().0, 1);
+    assert_eq!(parse_grammar_file("S -> a\nS a\n").unwrap_err().0, 2);
+  }
+
+  #[test]
+  fn cycle_lines_count_the_declarations() {
+    let productions = lines(&["%left +", "S -> A", "A -> S | a", "E -> E + E | id"]);
+    let diagnostics = generate_parser(productions, generate_rust_parser).err().unwrap();
+
+    assert_eq!(diagnostics[0].productions[0].0, 1);
+  }
+
+  #[test]
+  fn conflicts_of_the_expanded_grammar() {
+    let productions = lines(&["%left +", "S -> A a", "A -> a | a b", "E -> E + E | id"]);
+    let diagnostics = generate_parser(productions, generate_rust_parser).err().unwrap();
+
+    assert_eq!(diagnostics[0].message, "conflicto en M[A, a] entre A -> a y A -> a b");
+    assert!(diagnostics[0].productions.is_empty());
+  }
+
+  #[test]
+  fn undeclared_operator() {
+    let productions = lines(&["%left +", "E -> E + E | E * E | id"]);
+    let diagnostics = generate_parser(productions, generate_rust_parser).err().unwrap();
+
+    assert!(diagnostics[0].message.contains("no tiene precedencia declarada"));
+    assert!(diagnostics[0].productions.is_empty());
+  }
+}
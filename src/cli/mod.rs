@@ -0,0 +1,93 @@
+use std::env;
+use std::str::FromStr;
+
+/// Argumentos de la línea de comandos: el modo de ejecución, las opciones
+/// de la forma `--nombre valor` o `--nombre=valor` y los demás argumentos
+/// (por ejemplo, nombres de archivos).
+pub struct Arguments {
+  /// Primer argumento, que elige el modo de ejecución.
+  pub mode: Option<String>,
+  /// Argumentos después del modo que no son opciones, en orden.
+  pub positional: Vec<String>,
+  options: Vec<(String, String)>,
+}
+
+impl Arguments {
+  /// Lee los argumentos con los que se ejecutó el programa.
+  pub fn from_env() -> Self {
+    Arguments::parse(env::args().skip(1).collect())
+  }
+
+  /// Separa una lista de argumentos en modo, opciones y argumentos
+  /// posicionales. Una opción sin valor (seguida de otra opción o al final)
+  /// tiene como valor el texto vacío.
+  pub fn parse(args: Vec<String>) -> Self {
+    let mut args = args.into_iter().peekable();
+    let mut options = vec![];
+    let mut positional = vec![];
+
+    let mode = match args.peek() {
+      Some(first) if !first.starts_with("--") => args.next(),
+      _ => None,
+    };
+
+    while let Some(arg) = args.next() {
+      let name = match arg.strip_prefix("--") {
+        Some(name) => name,
+        None => {
+          positional.push(arg);
+          continue;
+        },
+      };
+
+      if let Some(position) = name.find('=') {
+        options.push((
+          String::from(&name[..position]), String::from(&name[position + 1..]),
+        ));
+        continue;
+      }
+
+      let value = match args.peek() {
+        Some(next) if !next.starts_with("--") => args.next().unwrap(),
+        _ => String::new(),
+      };
+      options.push((String::from(name), value));
+    }
+
+    Arguments { mode, positional, options }
+  }
+
+  /// Regresa el último valor de la opción, si fue dada.
+  pub fn value(&self, name: &str) -> Option<&str> {
+    self.options
+      .iter()
+      .rev()
+      .find(|(option, _)| option == name)
+      .map(|(_, value)| value.as_str())
+  }
+
+  /// Regresa `true` si la opción fue dada, con o sin valor.
+  pub fn has(&self, name: &str) -> bool {
+    self.value(name).is_some()
+  }
+
+  /// Regresa todos los valores de una opción que se puede repetir.
+  pub fn values(&self, name: &str) -> Vec<&str> {
+    self.options
+      .iter()
+      .filter(|(option, _)| option == name)
+      .map(|(_, value)| value.as_str())
+      .collect()
+  }
+
+  /// Regresa el valor de la opción convertido al tipo pedido, o `default` si
+  /// no fue dada. Regresa un error si el valor no es válido.
+  pub fn parsed<T: FromStr>(&self, name: &str, default: T) -> Result<T, String> {
+    match self.value(name) {
+      Some(value) => value.parse().map_err(|_| {
+        format!("Valor inválido para --{}: {}", name, value)
+      }),
+      None => Ok(default),
+    }
+  }
+}
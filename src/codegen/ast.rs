@@ -0,0 +1,613 @@
+use crate::analyzer::{LL1Analyzer};
+use crate::grammar::{Grammar};
+use crate::linter::{LintKind, lint_grammar};
+use crate::transform::{fresh_name};
+use super::{Identifiers, check_ll1, generate_rust_parser};
+
+/// Palabras reservadas de Rust, que no pueden ser nombres de campos.
+const KEYWORDS: &[&str] = &[
+  "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+  "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in",
+  "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+  "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+  "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Producción de la gramática anotada.
+struct Annotated {
+  left: String,
+  body: Vec<String>,
+  /// Variante del `enum` del no terminal.
+  variant: String,
+  /// Campo de cada símbolo del lado derecho, o `None` si se omite (`_`).
+  fields: Vec<Option<String>>,
+}
+
+/// Origen de una producción de la gramática LL(1) en la gramática anotada.
+#[derive(Clone, Copy)]
+enum Origin {
+  /// La misma producción anotada.
+  Direct(usize),
+  /// `A -> β APrime`, que viene de la producción anotada `A -> β`.
+  Base(usize),
+  /// `APrime -> α APrime`, que viene de la producción anotada `A -> A α`.
+  Step(usize),
+  /// `APrime -> ε`, que termina la cadena de `APrime`.
+  End,
+}
+
+/// Genera un módulo de Rust con el parser dirigido por tabla de
+/// `generate_rust_parser` y un árbol de sintaxis abstracta tipado para una
+/// gramática anotada. Cada producción termina con `=> Variante(campo, ...)`:
+/// un nombre de campo (o `_` para omitirlo) por símbolo del lado derecho.
+///
+/// La recursión inmediata por la izquierda se elimina para el parser, igual
+/// que con `remove_left_recursion`, pero los `APrime` no aparecen en el
+/// árbol: `E -> E + T` se convierte en un nodo con `E` a la izquierda,
+/// asociado por la izquierda.
+///
+/// Regresa un error si falta una anotación o es inválida, o si la gramática
+/// sin recursión inmediata por la izquierda no es LL(1).
+pub fn generate_rust_ast_parser(productions: &[String]) -> Result<String, String> {
+  let annotated = parse_annotations(productions)?;
+  let (lines, origins) = without_left_recursion(&annotated)?;
+  let mut grammar = Grammar::from_productions(lines);
+
+  let cycles: Vec<String> = lint_grammar(&grammar)
+    .into_iter()
+    .filter(|lint| matches!(lint.kind, LintKind::Cycle))
+    .map(|lint| lint.message)
+    .collect();
+
+  if !cycles.is_empty() {
+    return Err(format!("La gramática tiene ciclos: {}", cycles.join(", ")));
+  }
+
+  grammar.find_sets();
+
+  let mut analyzer = LL1Analyzer::new(&mut grammar);
+  analyzer.build_table();
+  check_ll1(&analyzer)?;
+
+  let identifiers = Identifiers::new(&analyzer);
+  let mut code = generate_rust_parser(&analyzer)?;
+  code.push_str(&ast_entry_points(&identifiers.non_terminals[0]));
+  code.push_str(&ast_module(&analyzer, &identifiers, &annotated, &origins));
+
+  Ok(code)
+}
+
+/// Lee las producciones anotadas, con el mismo formato que el archivo de
+/// entrada: `|` separa alternativas y una línea que empieza con `|` continúa
+/// la anterior.
+fn parse_annotations(productions: &[String]) -> Result<Vec<Annotated>, String> {
+  let mut annotated: Vec<Annotated> = vec![];
+
+  for production in productions {
+    let (left, alternatives) = match production.trim().strip_prefix('|') {
+      Some(rest) => match annotated.last() {
+        Some(previous) => (previous.left.to_owned(), rest),
+        None => return Err(format!("`{}`: `|` sin una producción anterior", production.trim())),
+      },
+      None => match production.split_once(" -> ") {
+        Some((left, rest)) => (left.trim().to_owned(), rest),
+        None => return Err(format!("`{}` no es una producción", production.trim())),
+      },
+    };
+
+    let elements: Vec<&str> = alternatives.split_whitespace().collect();
+    for alternative in elements.split(|el| *el == "|") {
+      let arrow = alternative.iter().position(|el| *el == "=>");
+      let body: Vec<String> = match &alternative[..arrow.unwrap_or(alternative.len())] {
+        [] | ["ε"] | ["'", "'"] => vec![],
+        body => body.iter().map(|el| el.to_string()).collect(),
+      };
+      let written = if body.is_empty() {
+        format!("{} -> ε", left)
+      } else {
+        format!("{} -> {}", left, body.join(" "))
+      };
+
+      let annotation = match arrow {
+        Some(arrow) => alternative[arrow + 1..].join(" "),
+        None => return Err(format!(
+          "`{}` no tiene anotación: se escribe `=> Variante(campo, ...)` al final", written,
+        )),
+      };
+      let (variant, fields) = parse_annotation(&annotation, body.len(), &written)?;
+
+      if annotated.iter().any(|other| other.left == left && other.variant == variant) {
+        return Err(format!("`{}`: `{}` ya tiene la variante `{}`", written, left, variant));
+      }
+
+      annotated.push(Annotated { left: left.to_owned(), body, variant, fields });
+    }
+  }
+
+  Ok(annotated)
+}
+
+/// Lee una anotación `Variante(campo, _, campo)`, o `Variante` si no tiene
+/// campos.
+fn parse_annotation(
+  annotation: &str, symbols: usize, production: &str,
+) -> Result<(String, Vec<Option<String>>), String> {
+  let (variant, fields) = match annotation.split_once('(') {
+    Some((variant, rest)) => match rest.trim_end().strip_suffix(')') {
+      Some(fields) if fields.trim().is_empty() => (variant.trim(), vec![]),
+      Some(fields) => (variant.trim(), fields.split(',').map(str::trim).collect()),
+      None => return Err(format!("`{}`: falta `)` en la anotación `{}`", production, annotation)),
+    },
+    None => (annotation.trim(), vec![]),
+  };
+
+  if !is_identifier(variant) || variant == "Self" {
+    return Err(format!("`{}`: `{}` no es un nombre de variante válido", production, variant));
+  }
+
+  if fields.len() != symbols {
+    return Err(format!(
+      "`{}`: la anotación debe tener un campo por símbolo del lado derecho ({}), pero tiene {}",
+      production, symbols, fields.len(),
+    ));
+  }
+
+  let mut names: Vec<Option<String>> = vec![];
+
+  for field in fields {
+    if field == "_" {
+      names.push(None);
+      continue;
+    }
+
+    if !is_identifier(field) || KEYWORDS.contains(&field) || field == "Self" {
+      return Err(format!("`{}`: `{}` no es un nombre de campo válido", production, field));
+    }
+
+    if names.contains(&Some(field.to_owned())) {
+      return Err(format!("`{}`: el campo `{}` está repetido", production, field));
+    }
+
+    names.push(Some(field.to_owned()));
+  }
+
+  Ok((variant.to_owned(), names))
+}
+
+/// Regresa si el texto es un identificador de Rust: letras, números y `_`,
+/// sin empezar con un número.
+fn is_identifier(text: &str) -> bool {
+  match text.chars().next() {
+    Some(first) if first.is_ascii_alphabetic() || first == '_' => {
+      text != "_" && text.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    },
+    _ => false,
+  }
+}
+
+/// Elimina la recursión inmediata por la izquierda: `A -> A α | β` se
+/// reemplaza por `A -> β APrime` y `APrime -> α APrime | ε`. Regresa las
+/// líneas de producciones de la gramática resultante y el origen de cada
+/// una.
+fn without_left_recursion(annotated: &[Annotated]) -> Result<(Vec<String>, Vec<Origin>), String> {
+  let mut non_terminals: Vec<String> = vec![];
+  for production in annotated {
+    if !non_terminals.contains(&production.left) {
+      non_terminals.push(production.left.to_owned());
+    }
+  }
+
+  let mut taken: Vec<String> = annotated
+    .iter()
+    .flat_map(|production| production.body.iter().cloned())
+    .chain(non_terminals.iter().cloned())
+    .collect();
+  let mut lines = vec![];
+  let mut origins = vec![];
+  let line = |left: &str, body: &[String]| if body.is_empty() {
+    format!("{} -> ' '", left)
+  } else {
+    format!("{} -> {}", left, body.join(" "))
+  };
+
+  for non_terminal in &non_terminals {
+    let (recursive, others): (Vec<usize>, Vec<usize>) = (0..annotated.len())
+      .filter(|index| annotated[*index].left == *non_terminal)
+      .partition(|index| annotated[*index].body.first() == Some(non_terminal));
+
+    if recursive.is_empty() {
+      for index in others {
+        lines.push(line(non_terminal, &annotated[index].body));
+        origins.push(Origin::Direct(index));
+      }
+      continue;
+    }
+
+    if others.is_empty() {
+      return Err(format!(
+        "`{}` solo tiene producciones recursivas por la izquierda: no deriva \
+        ninguna cadena de terminales",
+        non_terminal,
+      ));
+    }
+
+    let prime = fresh_name(non_terminal, &taken);
+    taken.push(prime.to_owned());
+
+    for index in others {
+      lines.push(line(non_terminal, &[annotated[index].body.as_slice(), &[prime.to_owned()]].concat()));
+      origins.push(Origin::Base(index));
+    }
+    for index in recursive {
+      lines.push(line(&prime, &[&annotated[index].body[1..], &[prime.to_owned()]].concat()));
+      origins.push(Origin::Step(index));
+    }
+    lines.push(line(&prime, &[]));
+    origins.push(Origin::End);
+  }
+
+  Ok((lines, origins))
+}
+
+/// Regresa las funciones que analizan la entrada y regresan el árbol de
+/// sintaxis abstracta del símbolo inicial.
+fn ast_entry_points(start: &str) -> String {
+  format!(
+    "\n/// Analiza la entrada como `parse` y convierte el árbol de parseo en el\n\
+    /// árbol de sintaxis abstracta.\n\
+    pub fn parse_ast(input: &str) -> Result<ast::{0}, ParseError> {{\n    \
+    parse(input).map(|tree| ast::{0}::from_tree(&tree))\n}}\n\n\
+    /// Lo mismo que `parse_ast`, a partir de tokens reconocidos por un lexer\n\
+    /// propio.\n\
+    pub fn parse_tokens_ast(tokens: impl IntoIterator<Item = Token>) -> Result<ast::{0}, ParseError> {{\n    \
+    parse_tokens(tokens).map(|tree| ast::{0}::from_tree(&tree))\n}}\n",
+    start,
+  )
+}
+
+/// Regresa el módulo `ast`: un `enum` por no terminal de la gramática
+/// anotada, con una variante por producción, y la conversión desde el árbol
+/// de parseo.
+fn ast_module(
+  analyzer: &LL1Analyzer, identifiers: &Identifiers, annotated: &[Annotated], origins: &[Origin],
+) -> String {
+  let grammar = &analyzer.grammar;
+  // Un no terminal de la gramática puede llamarse `Box`.
+  let boxed = if identifiers.non_terminals.iter().any(|el| el == "Box") {
+    "std::boxed::Box"
+  } else {
+    "Box"
+  };
+  let uses_tokens = annotated.iter().any(|production| {
+    production.body
+      .iter()
+      .zip(&production.fields)
+      .any(|(el, field)| field.is_some() && !grammar.non_terminals.contains(el))
+  });
+  let mut code = String::from(
+    "\n/// Árbol de sintaxis abstracta de la gramática anotada: un `enum` por no\n\
+    /// terminal, con una variante por producción.\n\
+    pub mod ast {\n",
+  );
+
+  let mut non_terminals: Vec<&String> = vec![];
+  for production in annotated {
+    if !non_terminals.contains(&&production.left) {
+      non_terminals.push(&production.left);
+    }
+  }
+
+  for (position, non_terminal) in non_terminals.into_iter().enumerate() {
+    let identifier = identifiers.non_terminal(analyzer, non_terminal);
+    let productions: Vec<usize> = (0..annotated.len())
+      .filter(|index| annotated[*index].left == *non_terminal)
+      .collect();
+
+    if position > 0 {
+      code.push('\n');
+    }
+    for index in &productions {
+      code.push_str(&format!("    /// {}\n", annotated_as_string(&annotated[*index])));
+    }
+    code.push_str(&format!(
+      "    #[derive(Clone, Debug, PartialEq, Eq)]\n    pub enum {} {{\n", identifier,
+    ));
+
+    for index in &productions {
+      let production = &annotated[*index];
+      let fields: Vec<String> = production.body
+        .iter()
+        .zip(&production.fields)
+        .filter_map(|(el, field)| field.as_ref().map(|field| (el, field)))
+        .map(|(el, field)| if grammar.non_terminals.contains(el) {
+          format!("            {}: {}<{}>,\n", field, boxed, identifiers.non_terminal(analyzer, el))
+        } else {
+          format!("            {}: super::Token,\n", field)
+        })
+        .collect();
+
+      code.push_str(&format!("        /// {}\n", written(production)));
+      if fields.is_empty() {
+        code.push_str(&format!("        {},\n", production.variant));
+      } else {
+        code.push_str(&format!(
+          "        {} {{\n{}        }},\n", production.variant, fields.concat(),
+        ));
+      }
+    }
+    code.push_str("    }\n");
+
+    code.push_str(&from_tree(analyzer, identifiers, annotated, origins, non_terminal, boxed));
+  }
+
+  code.push_str(
+    "\n    /// Regresa la producción y los hijos de un nodo del árbol de parseo.\n    \
+    fn node(tree: &super::Tree) -> (usize, &[super::Tree]) {\n        \
+    match tree {\n            \
+    super::Tree::Node {\n                \
+    production,\n                \
+    children,\n                \
+    ..\n            \
+    } => (*production, children),\n            \
+    super::Tree::Leaf(token) => {\n                \
+    unreachable!(\"se esperaba un nodo, se encontró el token `{}`\", token.text)\n            \
+    }\n        \
+    }\n    \
+    }\n",
+  );
+
+  if uses_tokens {
+    code.push_str(
+      "\n    /// Regresa el token de una hoja del árbol de parseo.\n    \
+      fn token(tree: &super::Tree) -> super::Token {\n        \
+      match tree {\n            \
+      super::Tree::Leaf(token) => token.clone(),\n            \
+      super::Tree::Node { .. } => unreachable!(\"se esperaba un token\"),\n        \
+      }\n    \
+      }\n",
+    );
+  }
+  code.push_str("}\n");
+
+  code
+}
+
+/// Regresa `from_tree`, que convierte el nodo del no terminal del árbol de
+/// parseo. Si el no terminal era recursivo por la izquierda, recorre la
+/// cadena de `APrime` y construye los nodos de izquierda a derecha.
+fn from_tree(
+  analyzer: &LL1Analyzer, identifiers: &Identifiers, annotated: &[Annotated],
+  origins: &[Origin], non_terminal: &str, boxed: &str,
+) -> String {
+  let grammar = &analyzer.grammar;
+  let identifier = identifiers.non_terminal(analyzer, non_terminal);
+  // Campos de la variante de la producción anotada. En `A -> A α`, el
+  // primer símbolo es el nodo construido hasta ahora y los hijos empiezan
+  // con α.
+  let fields = |production: &Annotated, recursive: bool| -> Vec<String> {
+    let skipped = if recursive { 1 } else { 0 };
+
+    production.body
+      .iter()
+      .zip(&production.fields)
+      .enumerate()
+      .filter_map(|(position, (el, field))| field.as_ref().map(|field| (position, el, field)))
+      .map(|(position, el, field)| match (position < skipped, grammar.non_terminals.contains(el)) {
+        (true, _) => format!("{}: {}::new(ast),", field, boxed),
+        (false, true) => format!(
+          "{}: {}::new({}::from_tree(&children[{}])),",
+          field, boxed, identifiers.non_terminal(analyzer, el), position - skipped,
+        ),
+        (false, false) => format!("{}: token(&children[{}]),", field, position - skipped),
+      })
+      .collect()
+  };
+  let arm = |index: usize, value: String, indent: &str| {
+    format!("{}// {}\n{}{} => {},\n", indent, grammar.production_as_string(index), indent, index, value)
+  };
+  let variant = |production: &Annotated, fields: Vec<String>, indent: &str| if fields.is_empty() {
+    format!("{}::{}", identifier, production.variant)
+  } else {
+    format!(
+      "{}::{} {{\n{}{}}}",
+      identifier,
+      production.variant,
+      fields.iter().map(|field| format!("{}    {}\n", indent, field)).collect::<String>(),
+      indent,
+    )
+  };
+
+  // Producciones de la gramática LL(1) del no terminal y, si era recursivo
+  // por la izquierda, de su `APrime`.
+  let base: Vec<usize> = (0..origins.len())
+    .filter(|index| grammar.sides.left[*index] == non_terminal)
+    .collect();
+  let steps: Vec<usize> = match origins[base[0]] {
+    Origin::Base(_) => {
+      let prime = grammar.production_body(base[0]).last().unwrap().to_owned();
+      (0..origins.len()).filter(|index| grammar.sides.left[*index] == prime).collect()
+    },
+    _ => vec![],
+  };
+  let recursive = !steps.is_empty();
+  let indent = "                ";
+
+  let mut arms = String::new();
+  let mut uses_children = recursive;
+
+  for index in &base {
+    let production = match origins[*index] {
+      Origin::Direct(production) | Origin::Base(production) => &annotated[production],
+      Origin::Step(_) | Origin::End => unreachable!("la producción es de un `APrime`"),
+    };
+    let fields = fields(production, false);
+
+    uses_children |= !fields.is_empty();
+    arms.push_str(&arm(*index, variant(production, fields, indent), indent));
+  }
+
+  let mut code = format!(
+    "\n    impl {} {{\n        \
+    /// Convierte el nodo de `{}` del árbol de parseo.\n        \
+    pub fn from_tree(tree: &super::Tree) -> Self {{\n            \
+    let (production, {}) = node(tree);\n\n",
+    identifier,
+    non_terminal,
+    if uses_children { "children" } else { "_" },
+  );
+
+  if !recursive {
+    code.push_str(&format!(
+      "            match production {{\n{}                \
+      _ => unreachable!(\"la producción no es de `{}`\"),\n            }}\n        }}\n    }}\n",
+      arms, non_terminal,
+    ));
+    return code;
+  }
+
+  let indent = "                    ";
+  let mut step_arms = String::new();
+
+  for index in &steps {
+    let value = match origins[*index] {
+      Origin::Step(production) => {
+        let production = &annotated[production];
+        variant(production, fields(production, true), indent)
+      },
+      Origin::End => String::from("return ast"),
+      Origin::Direct(_) | Origin::Base(_) => unreachable!("la producción no es de un `APrime`"),
+    };
+
+    step_arms.push_str(&arm(*index, value, indent));
+  }
+
+  code.push_str(&format!(
+    "            let mut ast = match production {{\n{}                \
+    _ => unreachable!(\"la producción no es de `{}`\"),\n            }};\n            \
+    let mut rest = children.last().unwrap();\n\n            \
+    loop {{\n                \
+    let (production, children) = node(rest);\n\n                \
+    ast = match production {{\n{}                    \
+    _ => unreachable!(\"la producción no es de `{}`\"),\n                \
+    }};\n                \
+    rest = children.last().unwrap();\n            \
+    }}\n        }}\n    }}\n",
+    arms, non_terminal, step_arms, non_terminal,
+  ));
+
+  code
+}
+
+/// Regresa la producción anotada como en la gramática.
+fn annotated_as_string(production: &Annotated) -> String {
+  let fields: Vec<&str> = production.fields
+    .iter()
+    .map(|field| field.as_deref().unwrap_or("_"))
+    .collect();
+
+  if fields.is_empty() {
+    format!("{} => {}", written(production), production.variant)
+  } else {
+    format!("{} => {}({})", written(production), production.variant, fields.join(", "))
+  }
+}
+
+/// Regresa la producción sin la anotación.
+fn written(production: &Annotated) -> String {
+  if production.body.is_empty() {
+    format!("{} -> ε", production.left)
+  } else {
+    format!("{} -> {}", production.left, production.body.join(" "))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{generate_rust_ast_parser, is_identifier};
+
+  /// Gramática de expresiones anotada, recursiva por la izquierda.
+  const EXPRESSIONS: [&str; 7] = [
+    "E -> E + T => Add(left, _, right)",
+    "  | E - T => Sub(left, _, right)",
+    "  | T => Term(term)",
+    "T -> T * F => Mul(left, _, right) | F => Factor(factor)",
+    "F -> ( E ) => Group(_, inner, _)",
+    "  | id => Id(name)",
+    "  | - F => Neg(_, operand)",
+  ];
+
+  fn generate(productions: &[&str]) -> Result<String, String> {
+    let productions: Vec<String> = productions.iter().map(|line| line.to_string()).collect();
+    generate_rust_ast_parser(&productions)
+  }
+
+  #[test]
+  fn one_enum_per_non_terminal() {
+    let code = generate(&EXPRESSIONS).unwrap();
+
+    assert!(code.contains("pub fn parse_ast(input: &str) -> Result<ast::E, ParseError> {"));
+    assert!(code.contains(
+      "        /// E -> E - T\n        Sub {\n            left: Box<E>,\n            right: Box<T>,\n        },\n"
+    ));
+    assert!(code.contains("        /// F -> id\n        Id {\n            name: super::Token,\n        },\n"));
+    assert!(code.contains("                    // EPrime -> - T EPrime\n                    2 => E::Sub {\n"));
+  }
+
+  #[test]
+  fn epsilon_variant_without_fields() {
+    let code = generate(&["S -> a S => More(_, rest)", "S -> ' ' => Done"]).unwrap();
+
+    assert!(code.contains("        /// S -> ε\n        Done,\n"));
+  }
+
+  #[test]
+  fn annotation_errors() {
+    assert_eq!(
+      generate(&["S -> a"]).unwrap_err(),
+      "`S -> a` no tiene anotación: se escribe `=> Variante(campo, ...)` al final",
+    );
+    assert_eq!(
+      generate(&["S -> a b => Pair(first)"]).unwrap_err(),
+      "`S -> a b`: la anotación debe tener un campo por símbolo del lado derecho (2), pero tiene 1",
+    );
+    assert_eq!(
+      generate(&["S -> a b => Pair(x, x)"]).unwrap_err(),
+      "`S -> a b`: el campo `x` está repetido",
+    );
+    assert_eq!(
+      generate(&["S -> a b => Pair(x, type)"]).unwrap_err(),
+      "`S -> a b`: `type` no es un nombre de campo válido",
+    );
+    assert_eq!(
+      generate(&["S -> a => A(x)", "S -> b => A(y)"]).unwrap_err(),
+      "`S -> b`: `S` ya tiene la variante `A`",
+    );
+    assert_eq!(
+      generate(&["S -> a => 1A(x)"]).unwrap_err(),
+      "`S -> a`: `1A` no es un nombre de variante válido",
+    );
+    assert_eq!(
+      generate(&["S -> a => A(x"]).unwrap_err(),
+      "`S -> a`: falta `)` en la anotación `A(x`",
+    );
+    assert_eq!(generate(&["| a => A(x)"]).unwrap_err(), "`| a => A(x)`: `|` sin una producción anterior");
+  }
+
+  #[test]
+  fn only_left_recursive_productions() {
+    assert_eq!(
+      generate(&["S -> S a => More(rest, _)", "T -> b => B(b)"]).unwrap_err(),
+      "`S` solo tiene producciones recursivas por la izquierda: no deriva ninguna cadena de terminales",
+    );
+  }
+
+  #[test]
+  fn identifiers() {
+    assert!(is_identifier("left"));
+    assert!(is_identifier("_left2"));
+    assert!(!is_identifier("_"));
+    assert!(!is_identifier("2left"));
+    assert!(!is_identifier("le-ft"));
+    assert!(!is_identifier(""));
+  }
+}
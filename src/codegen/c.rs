@@ -0,0 +1,679 @@
+use crate::analyzer::{LL1Analyzer};
+use super::{Identifiers, check_ll1, header_comment, snake_case};
+
+/// Declaraciones del encabezado que no dependen de la gramática.
+const C_DECLARATIONS: &str = r#"
+/* Token de la entrada: el terminal reconocido y su texto. */
+typedef struct {
+    Terminal terminal;
+    char *text;
+} Token;
+
+/* Árbol de parseo: cada nodo es un no terminal con el índice de la
+ * producción usada y sus hijos, y cada hoja es un token. */
+typedef struct Tree {
+    int is_leaf;
+    /* Solo en las hojas. */
+    Token token;
+    /* Solo en los nodos. */
+    NonTerminal non_terminal;
+    size_t production;
+    struct Tree *children;
+    size_t child_count;
+} Tree;
+
+/* Error de sintaxis. */
+typedef struct {
+    /* Posición (empezando en 1) del token donde se encontró el error. El
+     * final de la entrada tiene la posición siguiente al último token. */
+    size_t position;
+    /* Texto encontrado ("$" al final de la entrada). */
+    char *found;
+    /* Terminales que se esperaban en esa posición. */
+    Terminal expected[NUMBER_OF_TERMINALS];
+    size_t expected_count;
+} ParseError;
+
+/* Regresa 1 y guarda en `terminal` el terminal que se escribe como `text` en
+ * la gramática, o 0 si no hay ninguno. */
+int terminal_from_text(const char *text, Terminal *terminal);
+
+/* Regresa el texto del terminal en la gramática. */
+const char *terminal_text(Terminal terminal);
+
+/* Regresa el nombre del no terminal en la gramática. */
+const char *non_terminal_name(NonTerminal non_terminal);
+
+/* Analiza la entrada separándola por espacios, igual que el modo `test` de
+ * lexian. Regresa el árbol de parseo, que se libera con `tree_free`, o NULL
+ * si hay un error de sintaxis, que se guarda en `error` (si no es NULL) y se
+ * libera con `parse_error_free`. */
+Tree *parse(const char *input, ParseError *error);
+
+/* Lo mismo que `parse`, a partir de tokens ya reconocidos, por ejemplo por
+ * un lexer propio. El árbol guarda una copia del texto de los tokens. */
+Tree *parse_tokens(const Token *tokens, size_t count, ParseError *error);
+
+/* Libera el árbol regresado por `parse` o `parse_tokens`. */
+void tree_free(Tree *tree);
+
+/* Escribe el mensaje del error en el archivo, sin salto de línea. */
+void parse_error_print(const ParseError *error, FILE *file);
+
+/* Libera el texto guardado en el error. */
+void parse_error_free(ParseError *error);
+"#;
+
+/// Parte del código que no depende de la gramática: el parser predictivo
+/// que usa `PRODUCTIONS` y `TABLE`.
+const C_DRIVER: &str = r#"
+/* Token de la entrada del parser: `known` es 0 si el texto no es un
+ * terminal de la gramática. */
+typedef struct {
+    int known;
+    Terminal terminal;
+    const char *text;
+} Input;
+
+/* Símbolo por derivar junto con su nodo en el árbol. */
+typedef struct {
+    Symbol symbol;
+    Tree *node;
+} Entry;
+
+static void *allocate(size_t count, size_t size) {
+    void *memory = calloc(count > 0 ? count : 1, size);
+
+    if (memory == NULL) {
+        fputs("No hay memoria suficiente\n", stderr);
+        abort();
+    }
+
+    return memory;
+}
+
+static char *copy_text(const char *text) {
+    char *copy = allocate(strlen(text) + 1, 1);
+
+    strcpy(copy, text);
+    return copy;
+}
+
+int terminal_from_text(const char *text, Terminal *terminal) {
+    int index;
+
+    for (index = 0; index < NUMBER_OF_TERMINALS; index++) {
+        if (index != TERMINAL_END && strcmp(text, TEXTS_OF_TERMINALS[index]) == 0) {
+            *terminal = (Terminal) index;
+            return 1;
+        }
+    }
+
+    return 0;
+}
+
+const char *terminal_text(Terminal terminal) {
+    return TEXTS_OF_TERMINALS[terminal];
+}
+
+const char *non_terminal_name(NonTerminal non_terminal) {
+    return NAMES_OF_NON_TERMINALS[non_terminal];
+}
+
+/* Libera el contenido de un nodo, pero no el nodo. */
+static void tree_clear(Tree *tree) {
+    size_t index;
+
+    if (tree->is_leaf) {
+        free(tree->token.text);
+        return;
+    }
+
+    for (index = 0; index < tree->child_count; index++) {
+        tree_clear(&tree->children[index]);
+    }
+    free(tree->children);
+}
+
+void tree_free(Tree *tree) {
+    if (tree != NULL) {
+        tree_clear(tree);
+        free(tree);
+    }
+}
+
+/* Guarda el error con los terminales esperados: uno solo o, si `terminal`
+ * es negativo, los que tienen una casilla ocupada en la fila del no
+ * terminal. */
+static void set_error(
+    ParseError *error, size_t position, const char *found, int terminal, NonTerminal non_terminal
+) {
+    int index;
+
+    if (error == NULL) {
+        return;
+    }
+
+    error->position = position + 1;
+    error->found = copy_text(found);
+    error->expected_count = 0;
+
+    for (index = 0; index < NUMBER_OF_TERMINALS; index++) {
+        if (terminal >= 0 ? index == terminal : TABLE[non_terminal][index] >= 0) {
+            error->expected[error->expected_count++] = (Terminal) index;
+        }
+    }
+}
+
+/* Parser predictivo no recursivo: la pila guarda cada símbolo por derivar
+ * junto con su nodo en el árbol. */
+static Tree *drive(const Input *tokens, size_t count, ParseError *error) {
+    Tree *root = allocate(1, sizeof(Tree));
+    size_t capacity = 16;
+    size_t size = 0;
+    size_t position = 0;
+    Entry *stack = allocate(capacity, sizeof(Entry));
+
+    stack[size].symbol.is_terminal = 0;
+    stack[size].symbol.non_terminal = START;
+    stack[size].node = root;
+    size++;
+
+    for (;;) {
+        int known = position < count ? tokens[position].known : 1;
+        Terminal lookahead = position < count ? tokens[position].terminal : TERMINAL_END;
+        const char *text = position < count ? tokens[position].text : "$";
+        Entry entry;
+
+        if (size == 0) {
+            if (known && lookahead == TERMINAL_END) {
+                free(stack);
+                return root;
+            }
+
+            set_error(error, position, text, TERMINAL_END, START);
+            break;
+        }
+
+        entry = stack[--size];
+
+        if (entry.symbol.is_terminal) {
+            if (!known || lookahead != entry.symbol.terminal) {
+                set_error(error, position, text, entry.symbol.terminal, START);
+                break;
+            }
+
+            entry.node->is_leaf = 1;
+            entry.node->token.terminal = lookahead;
+            entry.node->token.text = copy_text(text);
+            position++;
+        } else {
+            NonTerminal non_terminal = entry.symbol.non_terminal;
+            int production = known ? TABLE[non_terminal][lookahead] : -1;
+            const Production *rule;
+            size_t index;
+
+            if (production < 0) {
+                set_error(error, position, text, -1, non_terminal);
+                break;
+            }
+
+            rule = &PRODUCTIONS[production];
+            entry.node->non_terminal = non_terminal;
+            entry.node->production = (size_t) production;
+            entry.node->child_count = rule->length;
+            entry.node->children = rule->length > 0 ? allocate(rule->length, sizeof(Tree)) : NULL;
+
+            if (size + rule->length > capacity) {
+                capacity = 2 * (size + rule->length);
+                stack = realloc(stack, capacity * sizeof(Entry));
+                if (stack == NULL) {
+                    fputs("No hay memoria suficiente\n", stderr);
+                    abort();
+                }
+            }
+
+            for (index = rule->length; index > 0; index--) {
+                stack[size].symbol = rule->body[index - 1];
+                stack[size].node = &entry.node->children[index - 1];
+                size++;
+            }
+        }
+    }
+
+    free(stack);
+    tree_free(root);
+    return NULL;
+}
+
+Tree *parse(const char *input, ParseError *error) {
+    char *copy = copy_text(input);
+    char *cursor = copy;
+    Input *tokens = allocate(strlen(input) / 2 + 1, sizeof(Input));
+    size_t count = 0;
+    Tree *tree;
+
+    for (;;) {
+        while (*cursor != '\0' && isspace((unsigned char) *cursor)) {
+            cursor++;
+        }
+        if (*cursor == '\0') {
+            break;
+        }
+
+        tokens[count].text = cursor;
+        while (*cursor != '\0' && !isspace((unsigned char) *cursor)) {
+            cursor++;
+        }
+        if (*cursor != '\0') {
+            *cursor++ = '\0';
+        }
+
+        tokens[count].known = terminal_from_text(tokens[count].text, &tokens[count].terminal);
+        count++;
+    }
+
+    tree = drive(tokens, count, error);
+    free(tokens);
+    free(copy);
+
+    return tree;
+}
+
+Tree *parse_tokens(const Token *tokens, size_t count, ParseError *error) {
+    Input *inputs = allocate(count, sizeof(Input));
+    size_t index;
+    Tree *tree;
+
+    for (index = 0; index < count; index++) {
+        inputs[index].known = 1;
+        inputs[index].terminal = tokens[index].terminal;
+        inputs[index].text = tokens[index].text;
+    }
+
+    tree = drive(inputs, count, error);
+    free(inputs);
+
+    return tree;
+}
+
+void parse_error_print(const ParseError *error, FILE *file) {
+    size_t index;
+
+    fprintf(
+        file, "Posición %lu: se encontró `%s` pero se esperaba ",
+        (unsigned long) error->position, error->found
+    );
+
+    if (error->expected_count == 1) {
+        fprintf(file, "`%s`", terminal_text(error->expected[0]));
+        return;
+    }
+
+    fputs("uno de:", file);
+    for (index = 0; index < error->expected_count; index++) {
+        fprintf(file, " %s", terminal_text(error->expected[index]));
+    }
+}
+
+void parse_error_free(ParseError *error) {
+    free(error->found);
+    error->found = NULL;
+}
+"#;
+
+/// Nombres que exporta el parser de C sin prefijo: tipos, funciones,
+/// arreglos y macros. Las constantes de los terminales y no terminales
+/// (`TERMINAL_*` y `NON_TERMINAL_*`) también se exportan.
+const C_TYPES: [&str; 7] = ["Terminal", "NonTerminal", "Symbol", "Production", "Token", "Tree", "ParseError"];
+const C_FUNCTIONS: [&str; 8] = [
+  "terminal_from_text", "terminal_text", "non_terminal_name", "parse", "parse_tokens", "tree_free",
+  "parse_error_print", "parse_error_free",
+];
+const C_CONSTANTS: [&str; 6] = [
+  "PRODUCTIONS", "TABLE", "START", "NUMBER_OF_TERMINALS", "NUMBER_OF_NON_TERMINALS", "NUMBER_OF_PRODUCTIONS",
+];
+
+/// Genera un parser LL(1) en C dirigido por tabla, independiente de lexian,
+/// con la misma interfaz que el de Rust: los símbolos como `enum`, las
+/// producciones y la tabla como arreglos, y `parse`/`parse_tokens`, que
+/// construyen el árbol de parseo o llenan un `ParseError`.
+///
+/// Todos los nombres que exporta llevan el prefijo, para poder enlazar
+/// varios parsers en el mismo programa: con `expr`, los tipos se llaman
+/// `ExprTree`, `ExprParseError`, etc., las funciones `expr_parse`,
+/// `expr_tree_free`, etc., y las constantes y macros `EXPR_TABLE`,
+/// `EXPR_TERMINAL_END`, etc.
+///
+/// Regresa el encabezado y el código fuente. `header` es el nombre del
+/// archivo del encabezado, que el código fuente incluye.
+///
+/// Regresa un error si el prefijo no es un identificador de C en snake_case
+/// o si la gramática no es LL(1).
+pub fn generate_c_parser(
+  analyzer: &LL1Analyzer, header: &str, prefix: &str,
+) -> Result<(String, String), String> {
+  let valid_prefix = prefix.starts_with(|character: char| character.is_ascii_lowercase())
+    && prefix.chars().all(|character| character.is_ascii_lowercase() || character.is_ascii_digit() || character == '_');
+
+  if !valid_prefix {
+    return Err(format!(
+      "El prefijo `{}` debe empezar con una letra minúscula y tener solo minúsculas, números y `_`",
+      prefix,
+    ));
+  }
+
+  check_ll1(analyzer)?;
+
+  let identifiers = Identifiers::new(analyzer);
+  let comment = header_comment(analyzer, "dirigido por tabla");
+  let guard = include_guard(header);
+
+  let mut declarations = format!("\n#ifndef {}\n#define {}\n\n", guard, guard);
+  declarations.push_str(
+    "#include <stddef.h>\n#include <stdio.h>\n\n#ifdef __cplusplus\nextern \"C\" {\n#endif\n",
+  );
+  declarations.push_str(&c_symbols(analyzer, &identifiers));
+  declarations.push_str(C_DECLARATIONS);
+  declarations.push_str(&format!(
+    "\n#ifdef __cplusplus\n}}\n#endif\n\n#endif /* {} */\n", guard,
+  ));
+
+  let mut source = format!(
+    "\n#include \"{}\"\n\n#include <ctype.h>\n#include <stdlib.h>\n#include <string.h>\n",
+    header,
+  );
+  source.push_str(&c_names(analyzer));
+  source.push_str(&c_productions(analyzer, &identifiers));
+  source.push_str(&c_table(analyzer, &identifiers));
+  source.push_str(C_DRIVER);
+
+  Ok((
+    comment.to_owned() + &with_prefix(&declarations, prefix),
+    comment + &with_prefix(&source, prefix),
+  ))
+}
+
+/// Agrega el prefijo a los nombres que exporta el parser. Los textos entre
+/// comillas y los comentarios `//`, que tienen los símbolos de la
+/// gramática, no se modifican.
+fn with_prefix(code: &str, prefix: &str) -> String {
+  let types: String = prefix
+    .split('_')
+    .map(|word| {
+      let mut characters = word.chars();
+      match characters.next() {
+        Some(first) => first.to_ascii_uppercase().to_string() + characters.as_str(),
+        None => String::new(),
+      }
+    })
+    .collect();
+  let constants = prefix.to_ascii_uppercase();
+  let rename = |name: &str| -> String {
+    if C_TYPES.contains(&name) {
+      format!("{}{}", types, name)
+    } else if C_FUNCTIONS.contains(&name) {
+      format!("{}_{}", prefix, name)
+    } else if C_CONSTANTS.contains(&name) || name.starts_with("TERMINAL_") || name.starts_with("NON_TERMINAL_") {
+      format!("{}_{}", constants, name)
+    } else {
+      name.to_owned()
+    }
+  };
+
+  let characters: Vec<char> = code.chars().collect();
+  let mut result = String::new();
+  let mut position = 0;
+
+  while position < characters.len() {
+    let character = characters[position];
+    let start = position;
+
+    if character == '"' {
+      position += 1;
+      while position < characters.len() && characters[position] != '"' {
+        position += if characters[position] == '\\' {2} else {1};
+      }
+      position = (position + 1).min(characters.len());
+    } else if character == '/' && characters.get(position + 1) == Some(&'/') {
+      while position < characters.len() && characters[position] != '\n' {
+        position += 1;
+      }
+    } else if character.is_ascii_alphabetic() || character == '_' {
+      while position < characters.len()
+        && (characters[position].is_ascii_alphanumeric() || characters[position] == '_') {
+        position += 1;
+      }
+      let name: String = characters[start..position].iter().collect();
+      result.push_str(&rename(&name));
+      continue;
+    } else {
+      position += 1;
+    }
+
+    result.extend(&characters[start..position]);
+  }
+
+  result
+}
+
+/// Regresa los `enum` de terminales y no terminales, los tipos de las
+/// producciones y las declaraciones de `PRODUCTIONS` y `TABLE`.
+fn c_symbols(analyzer: &LL1Analyzer, identifiers: &Identifiers) -> String {
+  let table = &analyzer.table;
+  let mut code = String::from(
+    "\n/* Terminales de la gramática. `TERMINAL_END` es el final de la entrada\n \
+    * (`$`). */\ntypedef enum {\n",
+  );
+
+  for (terminal, identifier) in table.terminals.iter().zip(&identifiers.terminals) {
+    code.push_str(&format!("    {}, // {}\n", terminal_constant(identifier), terminal));
+  }
+  code.push_str(&format!(
+    "}} Terminal;\n\n#define NUMBER_OF_TERMINALS {}\n\n\
+    /* No terminales de la gramática. */\ntypedef enum {{\n",
+    table.terminals.len(),
+  ));
+
+  for (non_terminal, identifier) in table.non_terminals.iter().zip(&identifiers.non_terminals) {
+    code.push_str(&format!("    {}, // {}\n", non_terminal_constant(identifier), non_terminal));
+  }
+  code.push_str(&format!(
+    "}} NonTerminal;\n\n#define NUMBER_OF_NON_TERMINALS {}\n\n\
+    /* Símbolo inicial de la gramática. */\n#define START {}\n",
+    table.non_terminals.len(),
+    non_terminal_constant(&identifiers.non_terminals[0]),
+  ));
+
+  code.push_str(&format!(
+    "\n/* Símbolo del lado derecho de una producción: un terminal si\n \
+    * `is_terminal` no es 0 y si no un no terminal. */\n\
+    typedef struct {{\n    int is_terminal;\n    Terminal terminal;\n    NonTerminal non_terminal;\n}} Symbol;\n\n\
+    /* Producción de la gramática: no terminal del lado izquierdo y símbolos\n \
+    * del lado derecho (ninguno si es EPSILON). */\n\
+    typedef struct {{\n    NonTerminal left;\n    const Symbol *body;\n    size_t length;\n}} Production;\n\n\
+    #define NUMBER_OF_PRODUCTIONS {}\n\n\
+    /* Producciones de la gramática. */\n\
+    extern const Production PRODUCTIONS[NUMBER_OF_PRODUCTIONS];\n\n\
+    /* Tabla de parseo: `TABLE[no terminal][terminal]` es el índice de la\n \
+    * producción a usar, o -1 si la casilla está vacía. */\n\
+    extern const int TABLE[NUMBER_OF_NON_TERMINALS][NUMBER_OF_TERMINALS];\n",
+    analyzer.grammar.sides.left.len(),
+  ));
+
+  code
+}
+
+/// Regresa los textos de los terminales y los nombres de los no terminales.
+fn c_names(analyzer: &LL1Analyzer) -> String {
+  let table = &analyzer.table;
+  let strings = |symbols: &[String]| -> String {
+    symbols.iter().map(|symbol| c_string(symbol)).collect::<Vec<_>>().join(", ")
+  };
+
+  format!(
+    "\nstatic const char *const TEXTS_OF_TERMINALS[NUMBER_OF_TERMINALS] = {{{}}};\n\n\
+    static const char *const NAMES_OF_NON_TERMINALS[NUMBER_OF_NON_TERMINALS] = {{{}}};\n",
+    strings(&table.terminals),
+    strings(&table.non_terminals),
+  )
+}
+
+/// Regresa el lado derecho de cada producción como un arreglo y el arreglo
+/// de producciones.
+fn c_productions(analyzer: &LL1Analyzer, identifiers: &Identifiers) -> String {
+  let grammar = &analyzer.grammar;
+  let mut bodies = String::from("\n");
+  let mut productions = String::from(
+    "\nconst Production PRODUCTIONS[NUMBER_OF_PRODUCTIONS] = {\n",
+  );
+
+  for (index, left) in grammar.sides.left.iter().enumerate() {
+    let body: Vec<String> = grammar.production_body(index)
+      .iter()
+      .map(|el| if grammar.non_terminals.contains(el) {
+        format!("{{0, TERMINAL_END, {}}}", non_terminal_constant(&identifiers.non_terminal(analyzer, el)))
+      } else {
+        format!("{{1, {}, START}}", terminal_constant(&identifiers.terminal(analyzer, el)))
+      })
+      .collect();
+
+    // C no permite arreglos vacíos: las producciones EPSILON usan NULL.
+    let array = if body.is_empty() {
+      String::from("NULL")
+    } else {
+      bodies.push_str(&format!(
+        "static const Symbol BODY_{}[] = {{{}}};\n", index, body.join(", "),
+      ));
+      format!("BODY_{}", index)
+    };
+
+    productions.push_str(&format!(
+      "    // {}\n    {{{}, {}, {}}},\n",
+      grammar.production_as_string(index),
+      non_terminal_constant(&identifiers.non_terminal(analyzer, left)),
+      array,
+      body.len(),
+    ));
+  }
+  productions.push_str("};\n");
+
+  bodies + &productions
+}
+
+/// Regresa la tabla de parseo como un arreglo.
+fn c_table(analyzer: &LL1Analyzer, identifiers: &Identifiers) -> String {
+  let table = &analyzer.table;
+  let mut code = String::from("\nconst int TABLE[NUMBER_OF_NON_TERMINALS][NUMBER_OF_TERMINALS] = {\n");
+
+  for (non_terminal, identifier) in table.non_terminals.iter().zip(&identifiers.non_terminals) {
+    let cells: Vec<String> = table.terminals
+      .iter()
+      .map(|terminal| match table.get(non_terminal, terminal) {
+        Some(index) => index.to_string(),
+        None => String::from("-1"),
+      })
+      .collect();
+
+    code.push_str(&format!("    // {}\n    {{{}}},\n", identifier, cells.join(", ")));
+  }
+  code.push_str("};\n");
+
+  code
+}
+
+/// Regresa la constante de un terminal, por ejemplo `TERMINAL_L_PAREN`.
+fn terminal_constant(identifier: &str) -> String {
+  format!("TERMINAL_{}", snake_case(identifier).to_ascii_uppercase())
+}
+
+/// Regresa la constante de un no terminal, por ejemplo
+/// `NON_TERMINAL_E_PRIME`.
+fn non_terminal_constant(identifier: &str) -> String {
+  format!("NON_TERMINAL_{}", snake_case(identifier).to_ascii_uppercase())
+}
+
+/// Regresa el texto como una cadena de C.
+fn c_string(text: &str) -> String {
+  let mut string = String::from("\"");
+
+  for character in text.chars() {
+    match character {
+      '"' | '\\' => {
+        string.push('\\');
+        string.push(character);
+      },
+      // Evita los trígrafos como `??)`.
+      '?' => string.push_str("\\?"),
+      _ => string.push(character),
+    }
+  }
+
+  string.push('"');
+  string
+}
+
+/// Regresa el nombre de la macro que evita incluir el encabezado dos veces,
+/// por ejemplo `PARSER_H` para `parser.h`.
+fn include_guard(header: &str) -> String {
+  let guard: String = header
+    .chars()
+    .map(|character| if character.is_ascii_alphanumeric() {
+      character.to_ascii_uppercase()
+    } else {
+      '_'
+    })
+    .collect();
+
+  match guard.chars().next() {
+    Some(first) if first.is_ascii_alphabetic() => guard,
+    _ => format!("PARSER_{}", guard),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::analyzer::LL1Analyzer;
+  use crate::examples;
+  use super::generate_c_parser;
+
+  fn generate(number: usize, prefix: &str) -> Result<(String, String), String> {
+    let mut grammar = examples::grammar(number);
+    let mut analyzer = LL1Analyzer::new(&mut grammar);
+    analyzer.build_table();
+    generate_c_parser(&analyzer, "expr.h", prefix)
+  }
+
+  #[test]
+  fn exported_names_with_prefix() {
+    let (header, source) = generate(1, "expr").unwrap();
+
+    assert!(header.contains("#ifndef EXPR_H\n"));
+    assert!(header.contains("} ExprTree;"));
+    assert!(header.contains("ExprTree *expr_parse(const char *input, ExprParseError *error);"));
+    assert!(header.contains("#define EXPR_START EXPR_NON_TERMINAL_E"));
+    assert!(header.contains("extern const int EXPR_TABLE["));
+    assert!(source.contains("#include \"expr.h\""));
+    assert!(source.contains("const ExprProduction EXPR_PRODUCTIONS["));
+    assert!(!header.contains(" parse(") && !source.contains(" TABLE["));
+  }
+
+  #[test]
+  fn texts_and_comments_keep_the_symbols() {
+    let (_, source) = generate(1, "expr").unwrap();
+
+    assert!(source.contains("\"id\""));
+    assert!(source.contains("// F -> ( E )"));
+  }
+
+  #[test]
+  fn invalid_prefix() {
+    assert!(generate(1, "Expr").unwrap_err().starts_with("El prefijo `Expr`"));
+    assert!(generate(1, "mi-parser").is_err());
+  }
+
+  #[test]
+  fn non_ll1_grammar() {
+    assert!(generate(4, "expr").unwrap_err().starts_with("La gramática no es LL(1)"));
+  }
+}
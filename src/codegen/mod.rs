@@ -0,0 +1,250 @@
+mod ast;
+mod c;
+mod rust;
+mod rust_descent;
+mod typescript;
+
+use super::analyzer::{LL1Analyzer};
+
+pub use ast::generate_rust_ast_parser;
+pub use c::generate_c_parser;
+pub use rust::generate_rust_parser;
+pub use rust_descent::generate_rust_descent_parser;
+pub use typescript::generate_typescript_parser;
+
+/// Nombres de los símbolos de la gramática como identificadores válidos en
+/// el código generado, en el mismo orden que la tabla de parseo.
+pub struct Identifiers {
+  /// Un identificador por columna de la tabla. `$` se llama `End`.
+  pub terminals: Vec<String>,
+  /// Un identificador por fila de la tabla.
+  pub non_terminals: Vec<String>,
+}
+
+impl Identifiers {
+  /// Crea los identificadores de los terminales y no terminales de la tabla
+  /// del analizador, sin repetir ninguno.
+  pub fn new(analyzer: &LL1Analyzer) -> Self {
+    let mut taken: Vec<String> = vec![String::from("End")];
+    let mut terminals = vec![];
+
+    for terminal in &analyzer.table.terminals {
+      if terminal == "$" {
+        terminals.push(String::from("End"));
+      } else {
+        terminals.push(unique_identifier(terminal, &mut taken));
+      }
+    }
+
+    let mut taken = vec![];
+    let non_terminals = analyzer.table.non_terminals
+      .iter()
+      .map(|non_terminal| unique_identifier(non_terminal, &mut taken))
+      .collect();
+
+    Identifiers { terminals, non_terminals }
+  }
+
+  /// Regresa el identificador del terminal (o `$`).
+  pub fn terminal(&self, analyzer: &LL1Analyzer, terminal: &str) -> String {
+    let position = analyzer.table.terminals.iter().position(|el| el == terminal).unwrap();
+    self.terminals[position].to_owned()
+  }
+
+  /// Regresa el identificador del no terminal.
+  pub fn non_terminal(&self, analyzer: &LL1Analyzer, non_terminal: &str) -> String {
+    let position = analyzer.table.non_terminals
+      .iter()
+      .position(|el| el == non_terminal)
+      .unwrap();
+    self.non_terminals[position].to_owned()
+  }
+}
+
+/// Regresa un error con los conflictos de la tabla si la gramática no es
+/// LL(1), porque el código generado necesita una sola producción por
+/// casilla.
+pub fn check_ll1(analyzer: &LL1Analyzer) -> Result<(), String> {
+  if analyzer.conflicts.is_empty() {
+    return Ok(());
+  }
+
+  let mut message = String::from("La gramática no es LL(1):");
+  for conflict in &analyzer.conflicts {
+    let productions: Vec<String> = conflict.productions
+      .iter()
+      .map(|index| analyzer.grammar.production_as_string(*index))
+      .collect();
+
+    message.push_str(&format!(
+      "\n  M[{}, {}]: {}",
+      conflict.non_terminal, conflict.terminal, productions.join(" | "),
+    ));
+  }
+
+  Err(message)
+}
+
+/// Regresa el comentario inicial del código generado, con la gramática. Usa
+/// `//`, que es válido en todos los lenguajes generados.
+pub fn header_comment(analyzer: &LL1Analyzer, kind: &str) -> String {
+  let mut code = format!(
+    "// Parser LL(1) {} generado por lexian. No lo modifiques a mano:\n\
+    // vuelve a generarlo a partir de la gramática.\n//\n",
+    kind,
+  );
+
+  for index in 0..analyzer.grammar.sides.left.len() {
+    code.push_str(&format!(
+      "// {:>3}. {}\n", index, analyzer.grammar.production_as_string(index),
+    ));
+  }
+
+  code
+}
+
+/// Convierte un identificador en CamelCase a snake_case, por ejemplo `EPrime`
+/// a `e_prime`. Como los identificadores empiezan con mayúscula y no tienen
+/// `_`, dos identificadores distintos nunca dan el mismo resultado.
+pub fn snake_case(identifier: &str) -> String {
+  let mut name = String::new();
+
+  for (position, character) in identifier.chars().enumerate() {
+    if character.is_ascii_uppercase() && position > 0 {
+      name.push('_');
+    }
+    name.push(character.to_ascii_lowercase());
+  }
+
+  name
+}
+
+/// Convierte un símbolo en un identificador en CamelCase que no esté entre
+/// los ocupados (agregando un número si hace falta) y lo agrega a ellos.
+fn unique_identifier(symbol: &str, taken: &mut Vec<String>) -> String {
+  let base = identifier(symbol);
+  let mut unique = base.to_owned();
+  let mut suffix = 2;
+
+  while taken.contains(&unique) {
+    unique = format!("{}{}", base, suffix);
+    suffix += 1;
+  }
+
+  taken.push(unique.to_owned());
+  unique
+}
+
+/// Convierte un símbolo en un identificador en CamelCase: las letras y
+/// números se conservan (con mayúscula al inicio de cada palabra) y los
+/// signos se reemplazan por su nombre, por ejemplo `(` por `LParen`.
+fn identifier(symbol: &str) -> String {
+  let mut identifier = String::new();
+  let mut capitalize = true;
+
+  for character in symbol.chars() {
+    if character.is_ascii_alphanumeric() {
+      if capitalize {
+        identifier.push(character.to_ascii_uppercase());
+      } else {
+        identifier.push(character);
+      }
+      capitalize = false;
+      continue;
+    }
+
+    capitalize = true;
+    identifier.push_str(match character {
+      '+' => "Plus",
+      '-' => "Minus",
+      '*' => "Star",
+      '/' => "Slash",
+      '(' => "LParen",
+      ')' => "RParen",
+      '[' => "LBracket",
+      ']' => "RBracket",
+      '{' => "LBrace",
+      '}' => "RBrace",
+      ',' => "Comma",
+      ';' => "Semicolon",
+      ':' => "Colon",
+      '.' => "Dot",
+      '=' => "Equals",
+      '<' => "Less",
+      '>' => "Greater",
+      '!' => "Bang",
+      '?' => "Question",
+      '^' => "Caret",
+      '%' => "Percent",
+      '&' => "Ampersand",
+      '|' => "Pipe",
+      '~' => "Tilde",
+      '#' => "Hash",
+      '@' => "At",
+      '\'' => "Quote",
+      '"' => "DoubleQuote",
+      '\\' => "Backslash",
+      '`' => "Backtick",
+      '$' => "Dollar",
+      _ => "",
+    });
+  }
+
+  match identifier.chars().next() {
+    None => String::from("Symbol"),
+    Some(first) if first.is_ascii_digit() => format!("T{}", identifier),
+    // `Self` es una palabra reservada.
+    _ if identifier == "Self" => String::from("SelfSymbol"),
+    _ => identifier,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::analyzer::LL1Analyzer;
+  use crate::examples;
+  use super::*;
+
+  #[test]
+  fn identifiers_of_symbols() {
+    let mut taken = vec![];
+    let identifiers: Vec<String> = ["id", "(", "==", "num_2", "2x", "", "self", "id"]
+      .iter()
+      .map(|symbol| unique_identifier(symbol, &mut taken))
+      .collect();
+
+    assert_eq!(identifiers, vec!["Id", "LParen", "EqualsEquals", "Num2", "T2x", "Symbol", "SelfSymbol", "Id2"]);
+  }
+
+  #[test]
+  fn identifiers_of_example() {
+    let mut grammar = examples::grammar(2);
+    let mut analyzer = LL1Analyzer::new(&mut grammar);
+    analyzer.build_table();
+    let identifiers = Identifiers::new(&analyzer);
+
+    assert_eq!(identifiers.terminals, vec!["LParen", "RParen", "A", "B", "End"]);
+    assert_eq!(identifiers.non_terminals, vec!["Goal", "A", "Two"]);
+    assert_eq!(identifiers.terminal(&analyzer, "$"), "End");
+    assert_eq!(identifiers.non_terminal(&analyzer, "two"), "Two");
+  }
+
+  #[test]
+  fn conflicts_of_non_ll1_grammar() {
+    let mut grammar = examples::grammar(4);
+    let mut analyzer = LL1Analyzer::new(&mut grammar);
+    analyzer.build_table();
+
+    assert_eq!(
+      check_ll1(&analyzer),
+      Err(String::from("La gramática no es LL(1):\n  M[A, a]: A -> a A | A -> a B")),
+    );
+  }
+
+  #[test]
+  fn snake_case_of_identifiers() {
+    assert_eq!(snake_case("EPrime"), "e_prime");
+    assert_eq!(snake_case("Goal"), "goal");
+    assert_eq!(snake_case("LParen2"), "l_paren2");
+  }
+}
@@ -0,0 +1,455 @@
+use crate::analyzer::{LL1Analyzer};
+use super::{Identifiers, check_ll1, header_comment};
+
+/// Parte del parser generado que no depende de la gramática: tokens, árbol
+/// de parseo, errores y el parser predictivo que usa `PRODUCTIONS` y
+/// `TABLE`.
+const TABLE_DRIVER: &str = r#"
+/// Regresa los terminales con una casilla ocupada en la fila del no
+/// terminal.
+fn expected(non_terminal: NonTerminal) -> Vec<Terminal> {
+    Terminal::ALL
+        .iter()
+        .copied()
+        .filter(|terminal| TABLE[non_terminal as usize][*terminal as usize].is_some())
+        .collect()
+}
+
+/// Elemento de la pila del parser.
+enum Entry {
+    /// Símbolo por derivar.
+    Symbol(Symbol),
+    /// Se terminó de reconocer el lado derecho de la producción del no
+    /// terminal.
+    Exit(NonTerminal, usize),
+}
+
+/// Parser predictivo no recursivo: toma un token a la vez y avisa al
+/// visitante de cada producción y token reconocidos.
+fn drive(
+    mut tokens: impl Iterator<Item = (Option<Terminal>, String)>,
+    visitor: &mut impl Visitor,
+) -> Result<(), ParseError> {
+    let mut current = tokens.next();
+    let mut stack = vec![Entry::Symbol(Symbol::NonTerminal(START))];
+    let mut position = 0;
+
+    loop {
+        let (lookahead, text) = match &current {
+            Some((terminal, text)) => (*terminal, text.as_str()),
+            None => (Some(Terminal::End), "$"),
+        };
+        let error = |expected: Vec<Terminal>| ParseError {
+            position: position + 1,
+            found: text.to_string(),
+            expected,
+        };
+
+        match stack.pop() {
+            None if lookahead == Some(Terminal::End) => return Ok(()),
+            None => return Err(error(vec![Terminal::End])),
+            Some(Entry::Exit(non_terminal, production)) => visitor.exit(non_terminal, production),
+            Some(Entry::Symbol(Symbol::Terminal(terminal))) => {
+                if lookahead != Some(terminal) {
+                    return Err(error(vec![terminal]));
+                }
+
+                visitor.token(&Token {
+                    terminal,
+                    text: text.to_string(),
+                });
+                current = tokens.next();
+                position += 1;
+            }
+            Some(Entry::Symbol(Symbol::NonTerminal(non_terminal))) => {
+                let cell = lookahead
+                    .and_then(|terminal| TABLE[non_terminal as usize][terminal as usize]);
+                let production = match cell {
+                    Some(production) => production,
+                    None => return Err(error(expected(non_terminal))),
+                };
+
+                visitor.enter(non_terminal, production);
+                stack.push(Entry::Exit(non_terminal, production));
+                for symbol in PRODUCTIONS[production].1.iter().rev() {
+                    stack.push(Entry::Symbol(*symbol));
+                }
+            }
+        }
+    }
+}
+"#;
+
+/// Funciones públicas de los parsers generados en Rust. Cada parser define
+/// `drive`, que analiza los tokens y avisa al visitante.
+pub const ENTRY_POINTS: &str = r#"
+/// Analiza la entrada separándola por espacios, igual que
+/// `LL1Analyzer::eval` de lexian. Un elemento que no es un terminal de la
+/// gramática produce un error de sintaxis al llegar a él.
+pub fn parse(input: &str) -> Result<Tree, ParseError> {
+    let mut builder = TreeBuilder::default();
+    parse_with(input, &mut builder)?;
+
+    Ok(builder.tree.unwrap())
+}
+
+/// Analiza una secuencia de tokens ya reconocidos, por ejemplo por un lexer
+/// propio.
+pub fn parse_tokens(tokens: impl IntoIterator<Item = Token>) -> Result<Tree, ParseError> {
+    let mut builder = TreeBuilder::default();
+    parse_tokens_with(tokens, &mut builder)?;
+
+    Ok(builder.tree.unwrap())
+}
+
+/// Lo mismo que `parse`, pero en lugar de construir el árbol avisa al
+/// visitante de cada evento del parser, en orden.
+pub fn parse_with(input: &str, visitor: &mut impl Visitor) -> Result<(), ParseError> {
+    notify_error(
+        drive(
+            input
+                .split_whitespace()
+                .map(|text| (Terminal::from_text(text), text.to_string())),
+            visitor,
+        ),
+        visitor,
+    )
+}
+
+/// Lo mismo que `parse_tokens`, pero en lugar de construir el árbol avisa
+/// al visitante de cada evento del parser, en orden. Los tokens se piden
+/// uno a la vez, así que pueden venir de un lexer que lee la entrada poco a
+/// poco.
+pub fn parse_tokens_with(
+    tokens: impl IntoIterator<Item = Token>,
+    visitor: &mut impl Visitor,
+) -> Result<(), ParseError> {
+    notify_error(
+        drive(
+            tokens
+                .into_iter()
+                .map(|token| (Some(token.terminal), token.text)),
+            visitor,
+        ),
+        visitor,
+    )
+}
+
+fn notify_error(result: Result<(), ParseError>, visitor: &mut impl Visitor) -> Result<(), ParseError> {
+    if let Err(error) = &result {
+        visitor.error(error);
+    }
+
+    result
+}
+
+/// Visitante que construye el árbol de parseo.
+#[derive(Default)]
+struct TreeBuilder {
+    /// Nodos que se están reconociendo, del más externo al más interno, con
+    /// los hijos reconocidos hasta ahora.
+    nodes: Vec<(NonTerminal, usize, Vec<Tree>)>,
+    tree: Option<Tree>,
+}
+
+impl Visitor for TreeBuilder {
+    fn enter(&mut self, non_terminal: NonTerminal, production: usize) {
+        self.nodes.push((non_terminal, production, vec![]));
+    }
+
+    fn token(&mut self, token: &Token) {
+        self.nodes.last_mut().unwrap().2.push(Tree::Leaf(token.clone()));
+    }
+
+    fn exit(&mut self, _non_terminal: NonTerminal, _production: usize) {
+        let (non_terminal, production, children) = self.nodes.pop().unwrap();
+        let node = Tree::Node {
+            non_terminal,
+            production,
+            children,
+        };
+
+        match self.nodes.last_mut() {
+            Some(parent) => parent.2.push(node),
+            None => self.tree = Some(node),
+        }
+    }
+}
+"#;
+
+/// Tipos comunes de los parsers generados en Rust.
+pub const TYPES: &str = r#"
+/// Token de la entrada: el terminal reconocido y su texto.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Token {
+    pub terminal: Terminal,
+    pub text: String,
+}
+
+/// Árbol de parseo: cada nodo es un no terminal con el índice de la
+/// producción usada y sus hijos, y cada hoja es un token.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Tree {
+    Node {
+        non_terminal: NonTerminal,
+        production: usize,
+        children: Vec<Tree>,
+    },
+    Leaf(Token),
+}
+
+/// Error de sintaxis.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// Posición (empezando en 1) del token donde se encontró el error. El
+    /// final de la entrada tiene la posición siguiente al último token.
+    pub position: usize,
+    /// Texto encontrado (`$` al final de la entrada).
+    pub found: String,
+    /// Terminales que se esperaban en esa posición.
+    pub expected: Vec<Terminal>,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let expected: Vec<&str> = self.expected.iter().map(|terminal| terminal.text()).collect();
+
+        match expected.as_slice() {
+            [single] => write!(
+                f,
+                "Posición {}: se encontró `{}` pero se esperaba `{}`",
+                self.position, self.found, single,
+            ),
+            _ => write!(
+                f,
+                "Posición {}: se encontró `{}` pero se esperaba uno de: {}",
+                self.position,
+                self.found,
+                expected.join(" "),
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Recibe los eventos del parser mientras analiza la entrada, para
+/// procesarla sin construir el árbol de parseo. Los métodos no hacen nada si
+/// no se implementan.
+pub trait Visitor {
+    /// Se empieza a reconocer el no terminal con la producción dada.
+    fn enter(&mut self, _non_terminal: NonTerminal, _production: usize) {}
+
+    /// Se reconoció el token.
+    fn token(&mut self, _token: &Token) {}
+
+    /// Se terminó de reconocer el no terminal, que se derivó con la
+    /// producción dada.
+    fn exit(&mut self, _non_terminal: NonTerminal, _production: usize) {}
+
+    /// Se encontró un error de sintaxis. Es el último evento.
+    fn error(&mut self, _error: &ParseError) {}
+}
+"#;
+
+/// Genera un módulo de Rust independiente de lexian con un parser LL(1)
+/// dirigido por tabla para la gramática del analizador: los terminales y no
+/// terminales como `enum`, las producciones y la tabla de parseo como
+/// arreglos estáticos, y un parser predictivo que acepta las mismas cadenas
+/// que `eval` y construye el árbol de parseo o avisa de cada evento a un
+/// `Visitor`.
+///
+/// Regresa un error si la gramática no es LL(1).
+pub fn generate_rust_parser(analyzer: &LL1Analyzer) -> Result<String, String> {
+  check_ll1(analyzer)?;
+
+  let identifiers = Identifiers::new(analyzer);
+  let mut code = header_comment(analyzer, "dirigido por tabla");
+  code.push_str(&rust_symbols(analyzer, &identifiers));
+  code.push_str(&rust_productions(analyzer, &identifiers));
+  code.push_str(&rust_table(analyzer, &identifiers));
+  code.push_str(TYPES);
+  code.push_str(ENTRY_POINTS);
+  code.push_str(TABLE_DRIVER);
+
+  Ok(code)
+}
+
+/// Regresa los `enum` de terminales, no terminales y símbolos, y el símbolo
+/// inicial.
+pub fn rust_symbols(analyzer: &LL1Analyzer, identifiers: &Identifiers) -> String {
+  let terminals = &analyzer.table.terminals;
+  let non_terminals = &analyzer.table.non_terminals;
+  let mut code = String::from(
+    "\n/// Terminales de la gramática. `End` es el final de la entrada (`$`).\n\
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]\n\
+    pub enum Terminal {\n",
+  );
+
+  for (terminal, identifier) in terminals.iter().zip(&identifiers.terminals) {
+    code.push_str(&format!("    /// `{}`\n    {},\n", terminal, identifier));
+  }
+  code.push_str("}\n\nimpl Terminal {\n");
+  code.push_str(&format!(
+    "    /// Todos los terminales, en el orden de las columnas de la tabla.\n    \
+    pub const ALL: [Terminal; {}] = [{}];\n\n",
+    terminals.len(),
+    identifiers.terminals
+      .iter()
+      .map(|identifier| format!("Terminal::{}", identifier))
+      .collect::<Vec<_>>()
+      .join(", "),
+  ));
+
+  code.push_str(
+    "    /// Regresa el terminal que se escribe como `text` en la gramática.\n    \
+    pub fn from_text(text: &str) -> Option<Terminal> {\n        match text {\n",
+  );
+  for (terminal, identifier) in terminals.iter().zip(&identifiers.terminals) {
+    if terminal != "$" {
+      code.push_str(&format!(
+        "            {:?} => Some(Terminal::{}),\n", terminal, identifier,
+      ));
+    }
+  }
+  code.push_str("            _ => None,\n        }\n    }\n\n");
+
+  code.push_str(
+    "    /// Regresa el texto del terminal en la gramática.\n    \
+    pub fn text(self) -> &'static str {\n        match self {\n",
+  );
+  for (terminal, identifier) in terminals.iter().zip(&identifiers.terminals) {
+    code.push_str(&format!(
+      "            Terminal::{} => {:?},\n", identifier, terminal,
+    ));
+  }
+  code.push_str("        }\n    }\n}\n");
+
+  code.push_str(
+    "\n/// No terminales de la gramática.\n\
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]\n\
+    pub enum NonTerminal {\n",
+  );
+  for identifier in &identifiers.non_terminals {
+    code.push_str(&format!("    {},\n", identifier));
+  }
+  code.push_str(
+    "}\n\nimpl NonTerminal {\n    \
+    /// Regresa el nombre del no terminal en la gramática.\n    \
+    pub fn name(self) -> &'static str {\n        match self {\n",
+  );
+  for (non_terminal, identifier) in non_terminals.iter().zip(&identifiers.non_terminals) {
+    code.push_str(&format!(
+      "            NonTerminal::{} => {:?},\n", identifier, non_terminal,
+    ));
+  }
+  code.push_str("        }\n    }\n}\n");
+
+  code.push_str(&format!(
+    "\n/// Símbolo del lado derecho de una producción.\n\
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]\n\
+    pub enum Symbol {{\n    Terminal(Terminal),\n    NonTerminal(NonTerminal),\n}}\n\n\
+    /// Símbolo inicial de la gramática.\n\
+    pub const START: NonTerminal = NonTerminal::{};\n",
+    identifiers.non_terminals[0],
+  ));
+
+  code
+}
+
+/// Regresa las producciones como un arreglo estático.
+fn rust_productions(analyzer: &LL1Analyzer, identifiers: &Identifiers) -> String {
+  let grammar = &analyzer.grammar;
+  let mut code = format!(
+    "\n/// Producciones de la gramática: no terminal del lado izquierdo y\n\
+    /// símbolos del lado derecho (ninguno si es EPSILON).\n\
+    pub static PRODUCTIONS: [(NonTerminal, &[Symbol]); {}] = [\n",
+    grammar.sides.left.len(),
+  );
+
+  for (index, left) in grammar.sides.left.iter().enumerate() {
+    let body: Vec<String> = grammar.production_body(index)
+      .iter()
+      .map(|el| if grammar.non_terminals.contains(el) {
+        format!("Symbol::NonTerminal(NonTerminal::{})", identifiers.non_terminal(analyzer, el))
+      } else {
+        format!("Symbol::Terminal(Terminal::{})", identifiers.terminal(analyzer, el))
+      })
+      .collect();
+
+    code.push_str(&format!(
+      "    // {}\n    (NonTerminal::{}, &[{}]),\n",
+      grammar.production_as_string(index),
+      identifiers.non_terminal(analyzer, left),
+      body.join(", "),
+    ));
+  }
+  code.push_str("];\n");
+
+  code
+}
+
+/// Regresa la tabla de parseo como un arreglo estático.
+fn rust_table(analyzer: &LL1Analyzer, identifiers: &Identifiers) -> String {
+  let table = &analyzer.table;
+  let mut code = format!(
+    "\n/// Tabla de parseo: `TABLE[no terminal][terminal]` es el índice de la\n\
+    /// producción a usar, en el orden de `NonTerminal` y `Terminal`.\n\
+    pub static TABLE: [[Option<usize>; {}]; {}] = [\n",
+    table.terminals.len(),
+    table.non_terminals.len(),
+  );
+
+  for (non_terminal, identifier) in table.non_terminals.iter().zip(&identifiers.non_terminals) {
+    let cells: Vec<String> = table.terminals
+      .iter()
+      .map(|terminal| match table.get(non_terminal, terminal) {
+        Some(index) => format!("Some({})", index),
+        None => String::from("None"),
+      })
+      .collect();
+
+    code.push_str(&format!("    // {}\n    [{}],\n", identifier, cells.join(", ")));
+  }
+  code.push_str("];\n");
+
+  code
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::analyzer::LL1Analyzer;
+  use crate::examples;
+  use super::generate_rust_parser;
+
+  fn generate(number: usize) -> Result<String, String> {
+    let mut grammar = examples::grammar(number);
+    let mut analyzer = LL1Analyzer::new(&mut grammar);
+    analyzer.build_table();
+    generate_rust_parser(&analyzer)
+  }
+
+  #[test]
+  fn table_and_productions_as_static_arrays() {
+    let code = generate(2).unwrap();
+
+    assert!(code.contains("pub const START: NonTerminal = NonTerminal::Goal;"));
+    assert!(code.contains("pub static PRODUCTIONS: [(NonTerminal, &[Symbol]); 5] = ["));
+    assert!(code.contains("    (NonTerminal::Two, &[Symbol::Terminal(Terminal::B)]),\n"));
+    assert!(code.contains("pub static TABLE: [[Option<usize>; 5]; 3] = ["));
+    assert!(code.contains("    // A\n    [Some(1), None, Some(2), Some(2), None],\n"));
+    assert!(code.contains("            \"a\" => Some(Terminal::A),\n"));
+  }
+
+  #[test]
+  fn epsilon_production_has_no_symbols() {
+    let code = generate(1).unwrap();
+
+    assert!(code.contains("    // EPrime -> ε\n    (NonTerminal::EPrime, &[]),\n"));
+  }
+
+  #[test]
+  fn non_ll1_grammar() {
+    assert!(generate(4).unwrap_err().starts_with("La gramática no es LL(1)"));
+  }
+}
@@ -0,0 +1,228 @@
+use crate::analyzer::{LL1Analyzer};
+use super::{Identifiers, check_ll1, header_comment, snake_case};
+use super::rust::{ENTRY_POINTS, TYPES, rust_symbols};
+
+/// Parte del parser recursivo que no depende de la gramática.
+const DESCENT_PARSER: &str = r#"
+/// Analiza los tokens a partir del símbolo inicial y avisa al visitante de
+/// cada producción y token reconocidos.
+fn drive(
+    tokens: impl Iterator<Item = (Option<Terminal>, String)>,
+    visitor: &mut impl Visitor,
+) -> Result<(), ParseError> {
+    Parser::new(tokens, visitor).parse()
+}
+
+/// Parser de descenso recursivo: una función por no terminal.
+struct Parser<'v, I, V> {
+    tokens: I,
+    /// Siguiente token, o `None` al final de la entrada.
+    current: Option<(Option<Terminal>, String)>,
+    position: usize,
+    visitor: &'v mut V,
+}
+
+impl<'v, I: Iterator<Item = (Option<Terminal>, String)>, V: Visitor> Parser<'v, I, V> {
+    fn new(mut tokens: I, visitor: &'v mut V) -> Self {
+        let current = tokens.next();
+
+        Parser {
+            tokens,
+            current,
+            position: 0,
+            visitor,
+        }
+    }
+
+    /// Terminal del siguiente token (`End` al final de la entrada), o
+    /// `None` si el token no es un terminal de la gramática.
+    fn lookahead(&self) -> Option<Terminal> {
+        match &self.current {
+            Some((terminal, _)) => *terminal,
+            None => Some(Terminal::End),
+        }
+    }
+
+    fn error(&self, expected: &[Terminal]) -> ParseError {
+        ParseError {
+            position: self.position + 1,
+            found: match &self.current {
+                Some((_, text)) => text.to_string(),
+                None => String::from("$"),
+            },
+            expected: expected.to_vec(),
+        }
+    }
+
+    /// Consume el siguiente token si es el terminal dado.
+    fn expect(&mut self, terminal: Terminal) -> Result<(), ParseError> {
+        if self.lookahead() != Some(terminal) {
+            return Err(self.error(&[terminal]));
+        }
+
+        let (_, text) = std::mem::replace(&mut self.current, self.tokens.next()).unwrap();
+        self.position += 1;
+        self.visitor.token(&Token { terminal, text });
+
+        Ok(())
+    }
+"#;
+
+/// Genera un módulo de Rust independiente de lexian con un parser de
+/// descenso recursivo para la gramática del analizador: una función por no
+/// terminal que elige la producción con un `match` sobre el siguiente token,
+/// con los casos de la tabla de parseo (derivados de FIRST y FOLLOW). Usa
+/// los mismos tipos que `generate_rust_parser`.
+///
+/// Regresa un error si la gramática no es LL(1).
+pub fn generate_rust_descent_parser(analyzer: &LL1Analyzer) -> Result<String, String> {
+  check_ll1(analyzer)?;
+
+  let identifiers = Identifiers::new(analyzer);
+  let start = function_name(&identifiers.non_terminals[0]);
+  let mut code = header_comment(analyzer, "de descenso recursivo");
+  code.push_str(&rust_symbols(analyzer, &identifiers));
+  code.push_str(TYPES);
+  code.push_str(ENTRY_POINTS);
+  code.push_str(DESCENT_PARSER);
+  code.push_str(&format!(
+    "\n    /// Analiza toda la entrada a partir del símbolo inicial.\n    \
+    fn parse(mut self) -> Result<(), ParseError> {{\n        \
+    self.{}()?;\n\n        \
+    match self.lookahead() {{\n            \
+    Some(Terminal::End) => Ok(()),\n            \
+    _ => Err(self.error(&[Terminal::End])),\n        \
+    }}\n    }}\n",
+    start,
+  ));
+
+  for (non_terminal, identifier) in analyzer.table.non_terminals.iter().zip(&identifiers.non_terminals) {
+    code.push_str(&non_terminal_function(analyzer, &identifiers, non_terminal, identifier));
+  }
+  code.push_str("}\n");
+
+  Ok(code)
+}
+
+/// Regresa la función que analiza un no terminal.
+fn non_terminal_function(
+  analyzer: &LL1Analyzer, identifiers: &Identifiers, non_terminal: &str, identifier: &str,
+) -> String {
+  let grammar = &analyzer.grammar;
+  let row = analyzer.table.row(non_terminal);
+  let indexes = grammar.get_indexes_in_non_terminals(&String::from(non_terminal));
+  let mut code = String::from("\n");
+
+  for index in &indexes {
+    code.push_str(&format!("    /// {}\n", grammar.production_as_string(*index)));
+  }
+  code.push_str(&format!(
+    "    fn {}(&mut self) -> Result<(), ParseError> {{\n        \
+    match self.lookahead() {{\n",
+    function_name(identifier),
+  ));
+
+  for index in indexes {
+    let patterns: Vec<String> = row
+      .iter()
+      .filter(|(_, cell)| *cell == index)
+      .map(|(terminal, _)| format!("Some(Terminal::{})", identifiers.terminal(analyzer, terminal)))
+      .collect();
+
+    // La producción no se usa con ningún terminal.
+    if patterns.is_empty() {
+      continue;
+    }
+
+    let mut children = String::new();
+
+    for el in grammar.production_body(index) {
+      if grammar.non_terminals.contains(&el) {
+        children.push_str(&format!(
+          "                self.{}()?;\n", function_name(&identifiers.non_terminal(analyzer, &el)),
+        ));
+      } else {
+        children.push_str(&format!(
+          "                self.expect(Terminal::{})?;\n", identifiers.terminal(analyzer, &el),
+        ));
+      }
+    }
+
+    code.push_str(&format!(
+      "            // {}\n            {} => {{\n                \
+      self.visitor.enter(NonTerminal::{}, {});\n\
+      {}                \
+      self.visitor.exit(NonTerminal::{}, {});\n                \
+      Ok(())\n            \
+      }}\n",
+      grammar.production_as_string(index),
+      patterns.join(" | "),
+      identifier,
+      index,
+      children,
+      identifier,
+      index,
+    ));
+  }
+
+  let expected: Vec<String> = row
+    .iter()
+    .map(|(terminal, _)| format!("Terminal::{}", identifiers.terminal(analyzer, terminal)))
+    .collect();
+  code.push_str(&format!(
+    "            _ => Err(self.error(&[{}])),\n        }}\n    }}\n",
+    expected.join(", "),
+  ));
+
+  code
+}
+
+/// Regresa el nombre de la función que analiza un no terminal, por ejemplo
+/// `parse_e_prime` para `EPrime`.
+fn function_name(identifier: &str) -> String {
+  format!("parse_{}", snake_case(identifier))
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::analyzer::LL1Analyzer;
+  use crate::examples;
+  use super::{function_name, generate_rust_descent_parser};
+
+  fn generate(number: usize) -> Result<String, String> {
+    let mut grammar = examples::grammar(number);
+    let mut analyzer = LL1Analyzer::new(&mut grammar);
+    analyzer.build_table();
+    generate_rust_descent_parser(&analyzer)
+  }
+
+  #[test]
+  fn one_function_per_non_terminal() {
+    let code = generate(1).unwrap();
+
+    for name in &["parse_e", "parse_e_prime", "parse_t", "parse_t_prime", "parse_f"] {
+      assert!(code.contains(&format!("    fn {}(&mut self) -> Result<(), ParseError> {{\n", name)));
+    }
+    assert!(code.contains("        self.parse_e()?;\n"));
+  }
+
+  #[test]
+  fn cases_from_the_table() {
+    let code = generate(1).unwrap();
+
+    assert!(code.contains("            // F -> ( E )\n            Some(Terminal::LParen) => {\n"));
+    assert!(code.contains("                self.expect(Terminal::LParen)?;\n                self.parse_e()?;\n"));
+    assert!(code.contains("            Some(Terminal::RParen) | Some(Terminal::End) => {\n"));
+  }
+
+  #[test]
+  fn function_names() {
+    assert_eq!(function_name("EPrime"), "parse_e_prime");
+    assert_eq!(function_name("Goal"), "parse_goal");
+  }
+
+  #[test]
+  fn non_ll1_grammar() {
+    assert!(generate(4).unwrap_err().starts_with("La gramática no es LL(1)"));
+  }
+}
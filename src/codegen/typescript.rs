@@ -0,0 +1,290 @@
+use crate::analyzer::{LL1Analyzer};
+use super::{Identifiers, check_ll1, header_comment};
+
+/// Parte del módulo que no depende de la gramática: tokens, árbol de parseo,
+/// errores y el parser predictivo que usa `PRODUCTIONS` y `TABLE`.
+const TYPESCRIPT_DRIVER: &str = r#"
+/** Token de la entrada: el terminal reconocido y su texto. */
+export interface Token {
+    terminal: Terminal;
+    text: string;
+}
+
+/**
+ * Árbol de parseo: cada nodo es un no terminal con el índice de la
+ * producción usada y sus hijos, y cada hoja es un token.
+ */
+export type Tree =
+    | { kind: "node"; nonTerminal: NonTerminal; production: number; children: Tree[] }
+    | { kind: "leaf"; token: Token };
+
+/** Error de sintaxis. */
+export class ParseError extends Error {
+    /**
+     * Posición (empezando en 1) del token donde se encontró el error. El
+     * final de la entrada tiene la posición siguiente al último token.
+     */
+    readonly position: number;
+    /** Texto encontrado (`$` al final de la entrada). */
+    readonly found: string;
+    /** Terminales que se esperaban en esa posición. */
+    readonly expected: Terminal[];
+
+    constructor(position: number, found: string, expected: Terminal[]) {
+        const texts = expected.map(terminalText);
+        super(
+            texts.length === 1
+                ? `Posición ${position}: se encontró \`${found}\` pero se esperaba \`${texts[0]}\``
+                : `Posición ${position}: se encontró \`${found}\` pero se esperaba uno de: ${texts.join(" ")}`,
+        );
+        this.name = "ParseError";
+        this.position = position;
+        this.found = found;
+        this.expected = expected;
+    }
+}
+
+/**
+ * Analiza la entrada separándola por espacios, igual que el modo `test` de
+ * lexian. Un elemento que no es un terminal de la gramática produce un error
+ * de sintaxis al llegar a él.
+ *
+ * @throws {ParseError} si la entrada no pertenece al lenguaje.
+ */
+export function parse(input: string): Tree {
+    return drive(
+        input
+            .split(/\s+/)
+            .filter((text) => text !== "")
+            .map((text): [Terminal | undefined, string] => [terminalFromText(text), text]),
+    );
+}
+
+/**
+ * Analiza una secuencia de tokens ya reconocidos, por ejemplo por un lexer
+ * propio.
+ *
+ * @throws {ParseError} si la entrada no pertenece al lenguaje.
+ */
+export function parseTokens(tokens: Token[]): Tree {
+    return drive(tokens.map((token): [Terminal | undefined, string] => [token.terminal, token.text]));
+}
+
+/** Regresa los terminales con una casilla ocupada en la fila del no terminal. */
+function expected(nonTerminal: NonTerminal): Terminal[] {
+    const terminals: Terminal[] = [];
+
+    TABLE[nonTerminal].forEach((cell, terminal) => {
+        if (cell !== null) {
+            terminals.push(terminal);
+        }
+    });
+
+    return terminals;
+}
+
+/**
+ * Parser predictivo no recursivo: la pila guarda cada símbolo por derivar
+ * junto con la lista de hijos de su padre, a la que se agrega al derivarlo.
+ */
+function drive(tokens: [Terminal | undefined, string][]): Tree {
+    const roots: Tree[] = [];
+    const stack: [GrammarSymbol, Tree[]][] = [[{ nonTerminal: START }, roots]];
+    let position = 0;
+
+    for (;;) {
+        const [lookahead, text]: [Terminal | undefined, string] =
+            position < tokens.length ? tokens[position] : [Terminal.End, "$"];
+        const entry = stack.pop();
+
+        if (entry === undefined) {
+            if (lookahead === Terminal.End) {
+                return roots[0];
+            }
+
+            throw new ParseError(position + 1, text, [Terminal.End]);
+        }
+
+        const [symbol, siblings] = entry;
+
+        if ("terminal" in symbol) {
+            if (lookahead !== symbol.terminal) {
+                throw new ParseError(position + 1, text, [symbol.terminal]);
+            }
+
+            siblings.push({ kind: "leaf", token: { terminal: symbol.terminal, text } });
+            position += 1;
+            continue;
+        }
+
+        const production = lookahead === undefined ? null : TABLE[symbol.nonTerminal][lookahead];
+
+        if (production === null) {
+            throw new ParseError(position + 1, text, expected(symbol.nonTerminal));
+        }
+
+        const children: Tree[] = [];
+        const body = PRODUCTIONS[production][1];
+
+        siblings.push({ kind: "node", nonTerminal: symbol.nonTerminal, production, children });
+        for (let index = body.length - 1; index >= 0; index--) {
+            stack.push([body[index], children]);
+        }
+    }
+}
+"#;
+
+/// Genera un módulo de TypeScript independiente de lexian con un parser
+/// LL(1) dirigido por tabla, con la misma interfaz que el de Rust: los
+/// símbolos como `enum`, las producciones y la tabla como arreglos, y
+/// `parse`/`parseTokens`, que regresan el árbol de parseo o lanzan un
+/// `ParseError`.
+///
+/// Regresa un error si la gramática no es LL(1).
+pub fn generate_typescript_parser(analyzer: &LL1Analyzer) -> Result<String, String> {
+  check_ll1(analyzer)?;
+
+  let identifiers = Identifiers::new(analyzer);
+  let mut code = header_comment(analyzer, "dirigido por tabla");
+  code.push_str(&typescript_symbols(analyzer, &identifiers));
+  code.push_str(&typescript_productions(analyzer, &identifiers));
+  code.push_str(&typescript_table(analyzer, &identifiers));
+  code.push_str(TYPESCRIPT_DRIVER);
+
+  Ok(code)
+}
+
+/// Regresa los `enum` de terminales y no terminales con sus textos, el tipo
+/// de los símbolos y el símbolo inicial.
+fn typescript_symbols(analyzer: &LL1Analyzer, identifiers: &Identifiers) -> String {
+  let table = &analyzer.table;
+  let strings = |symbols: &[String]| -> String {
+    symbols.iter().map(|symbol| format!("{:?}", symbol)).collect::<Vec<_>>().join(", ")
+  };
+  let mut code = String::from(
+    "\n/** Terminales de la gramática. `End` es el final de la entrada (`$`). */\n\
+    export enum Terminal {\n",
+  );
+
+  for (terminal, identifier) in table.terminals.iter().zip(&identifiers.terminals) {
+    code.push_str(&format!("    /** `{}` */\n    {},\n", terminal, identifier));
+  }
+  code.push_str("}\n\n/** No terminales de la gramática. */\nexport enum NonTerminal {\n");
+
+  for identifier in &identifiers.non_terminals {
+    code.push_str(&format!("    {},\n", identifier));
+  }
+  code.push_str(&format!(
+    "}}\n\nconst TERMINAL_TEXTS: readonly string[] = [{}];\n\n\
+    const NON_TERMINAL_NAMES: readonly string[] = [{}];\n",
+    strings(&table.terminals),
+    strings(&table.non_terminals),
+  ));
+
+  code.push_str(&format!(
+    "\n/** Regresa el terminal que se escribe como `text` en la gramática. */\n\
+    export function terminalFromText(text: string): Terminal | undefined {{\n    \
+    const index = TERMINAL_TEXTS.indexOf(text);\n\n    \
+    return index < 0 || index === Terminal.End ? undefined : index;\n}}\n\n\
+    /** Regresa el texto del terminal en la gramática. */\n\
+    export function terminalText(terminal: Terminal): string {{\n    \
+    return TERMINAL_TEXTS[terminal];\n}}\n\n\
+    /** Regresa el nombre del no terminal en la gramática. */\n\
+    export function nonTerminalName(nonTerminal: NonTerminal): string {{\n    \
+    return NON_TERMINAL_NAMES[nonTerminal];\n}}\n\n\
+    /** Símbolo del lado derecho de una producción. */\n\
+    export type GrammarSymbol = {{ terminal: Terminal }} | {{ nonTerminal: NonTerminal }};\n\n\
+    /** Símbolo inicial de la gramática. */\n\
+    export const START = NonTerminal.{};\n",
+    identifiers.non_terminals[0],
+  ));
+
+  code
+}
+
+/// Regresa las producciones como un arreglo.
+fn typescript_productions(analyzer: &LL1Analyzer, identifiers: &Identifiers) -> String {
+  let grammar = &analyzer.grammar;
+  let mut code = String::from(
+    "\n/**\n * Producciones de la gramática: no terminal del lado izquierdo y\n \
+    * símbolos del lado derecho (ninguno si es EPSILON).\n */\n\
+    export const PRODUCTIONS: readonly (readonly [NonTerminal, readonly GrammarSymbol[]])[] = [\n",
+  );
+
+  for (index, left) in grammar.sides.left.iter().enumerate() {
+    let body: Vec<String> = grammar.production_body(index)
+      .iter()
+      .map(|el| if grammar.non_terminals.contains(el) {
+        format!("{{ nonTerminal: NonTerminal.{} }}", identifiers.non_terminal(analyzer, el))
+      } else {
+        format!("{{ terminal: Terminal.{} }}", identifiers.terminal(analyzer, el))
+      })
+      .collect();
+
+    code.push_str(&format!(
+      "    // {}\n    [NonTerminal.{}, [{}]],\n",
+      grammar.production_as_string(index),
+      identifiers.non_terminal(analyzer, left),
+      body.join(", "),
+    ));
+  }
+  code.push_str("];\n");
+
+  code
+}
+
+/// Regresa la tabla de parseo como un arreglo.
+fn typescript_table(analyzer: &LL1Analyzer, identifiers: &Identifiers) -> String {
+  let table = &analyzer.table;
+  let mut code = String::from(
+    "\n/**\n * Tabla de parseo: `TABLE[no terminal][terminal]` es el índice de la\n \
+    * producción a usar, en el orden de `NonTerminal` y `Terminal`.\n */\n\
+    export const TABLE: readonly (readonly (number | null)[])[] = [\n",
+  );
+
+  for (non_terminal, identifier) in table.non_terminals.iter().zip(&identifiers.non_terminals) {
+    let cells: Vec<String> = table.terminals
+      .iter()
+      .map(|terminal| match table.get(non_terminal, terminal) {
+        Some(index) => index.to_string(),
+        None => String::from("null"),
+      })
+      .collect();
+
+    code.push_str(&format!("    // {}\n    [{}],\n", identifier, cells.join(", ")));
+  }
+  code.push_str("];\n");
+
+  code
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::analyzer::LL1Analyzer;
+  use crate::examples;
+  use super::generate_typescript_parser;
+
+  fn generate(number: usize) -> Result<String, String> {
+    let mut grammar = examples::grammar(number);
+    let mut analyzer = LL1Analyzer::new(&mut grammar);
+    analyzer.build_table();
+    generate_typescript_parser(&analyzer)
+  }
+
+  #[test]
+  fn table_and_productions_as_arrays() {
+    let code = generate(1).unwrap();
+
+    assert!(code.contains("export const START = NonTerminal.E;"));
+    assert!(code.contains(
+      "    // F -> id\n    [NonTerminal.F, [{ terminal: Terminal.Id }]],\n"
+    ));
+    assert!(code.contains("    // EPrime -> ε\n    [NonTerminal.EPrime, []],\n"));
+    assert!(code.contains("    // TPrime\n    [5, 4, null, 5, null, 5],\n"));
+  }
+
+  #[test]
+  fn non_ll1_grammar() {
+    assert!(generate(4).unwrap_err().starts_with("La gramática no es LL(1)"));
+  }
+}
@@ -0,0 +1,55 @@
+//! Archivos de ejemplo de `src/examples`, para las pruebas.
+
+use crate::grammar::{Grammar};
+use crate::input::{Input};
+
+const FILES: [&str; 7] = [
+  include_str!("input1.txt"),
+  include_str!("input2.txt"),
+  include_str!("input3.txt"),
+  include_str!("input4.txt"),
+  include_str!("input5.txt"),
+  include_str!("input6.txt"),
+  include_str!("input7.txt"),
+];
+
+/// Regresa las producciones y las entradas de `inputN.txt`.
+pub fn read(number: usize) -> (Vec<String>, Vec<Input>) {
+  let mut lines = FILES[number - 1].lines();
+  let counts: Vec<usize> = lines
+    .next()
+    .unwrap()
+    .split_whitespace()
+    .map(|count| count.parse().unwrap())
+    .collect();
+  let productions = lines.by_ref().take(counts[0]).map(String::from).collect();
+  let inputs = lines.take(counts[1]).map(Input::parse).collect();
+
+  (productions, inputs)
+}
+
+/// Regresa las producciones de `inputN.txt`.
+pub fn productions(number: usize) -> Vec<String> {
+  read(number).0
+}
+
+/// Regresa la gramática de `inputN.txt` con sus conjuntos FIRST y FOLLOW.
+pub fn grammar(number: usize) -> Grammar {
+  grammar_from(&productions(number))
+}
+
+/// Regresa la gramática de las producciones con sus conjuntos FIRST y
+/// FOLLOW.
+pub fn grammar_from(productions: &[impl AsRef<str>]) -> Grammar {
+  let mut grammar = unanalyzed(productions);
+  grammar.find_sets();
+  grammar
+}
+
+/// Regresa la gramática de las producciones sin calcular sus conjuntos, que
+/// no terminan si tiene ciclos o recursión por la izquierda oculta.
+pub fn unanalyzed(productions: &[impl AsRef<str>]) -> Grammar {
+  Grammar::from_productions(
+    productions.iter().map(|production| production.as_ref().to_owned()).collect(),
+  )
+}
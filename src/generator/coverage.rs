@@ -0,0 +1,377 @@
+use std::collections::{HashMap};
+use crate::analyzer::{LL1Analyzer};
+use crate::grammar::{Grammar, ShortestDerivation};
+
+/// Casilla de la tabla de parseo: no terminal y terminal.
+pub type Cell = (String, String);
+
+/// Resultado de generar entradas para cubrir la tabla de parseo.
+pub struct Coverage {
+  /// Cadenas válidas que, juntas, usan todas las casillas cubiertas.
+  pub sentences: Vec<Vec<String>>,
+  /// Casillas ocupadas de la tabla.
+  pub cells: Vec<Cell>,
+  /// Casillas para las que no se encontró ninguna cadena.
+  pub uncovered: Vec<Cell>,
+}
+
+/// Genera un conjunto pequeño de cadenas válidas que, juntas, usan todas las
+/// casillas ocupadas de la tabla de parseo (cada producción con cada
+/// terminal de preanálisis).
+///
+/// Para cada casilla se construye la cadena más corta que la usa, con ayuda
+/// de las derivaciones más cortas de la gramática. Después se eligen las
+/// cadenas que cubren más casillas nuevas hasta cubrirlas todas.
+pub fn find_coverage(analyzer: &mut LL1Analyzer) -> Coverage {
+  let builder = SentenceBuilder::new(analyzer.grammar);
+  let mut cells = vec![];
+  let mut candidates: Vec<(Vec<String>, Vec<Cell>)> = vec![];
+  let mut uncovered = vec![];
+
+  for non_terminal in &analyzer.table.non_terminals {
+    for (terminal, index) in analyzer.table.row(non_terminal) {
+      cells.push((non_terminal.to_owned(), terminal.to_owned()));
+
+      match builder.sentence_for_cell(non_terminal, terminal, index) {
+        Some(sentence) => candidates.push((sentence, vec![])),
+        None => uncovered.push((non_terminal.to_owned(), terminal.to_owned())),
+      }
+    }
+  }
+
+  // Se evalúa cada cadena para saber qué casillas usa realmente.
+  for (sentence, used) in candidates.iter_mut() {
+    let trace = analyzer.trace(&sentence.join(" "));
+    if !trace.accepted {
+      continue;
+    }
+
+    for step in trace.steps {
+      let (top, lookahead) = match (step.stack.last(), step.input.first()) {
+        (Some(top), Some(lookahead)) => (top, lookahead),
+        _ => continue,
+      };
+      let cell = (top.to_owned(), lookahead.to_owned());
+
+      if analyzer.grammar.non_terminals.contains(top) && !used.contains(&cell) {
+        used.push(cell);
+      }
+    }
+  }
+
+  // Se eligen las cadenas que cubren más casillas nuevas, prefiriendo las
+  // más cortas en caso de empate.
+  let mut covered: Vec<Cell> = vec![];
+  let mut sentences = vec![];
+  loop {
+    let best = candidates
+      .iter()
+      .map(|(sentence, used)| {
+        let new = used.iter().filter(|cell| !covered.contains(cell)).count();
+        (new, sentence, used)
+      })
+      .filter(|(new, _, _)| *new > 0)
+      .max_by(|a, b| a.0.cmp(&b.0).then(b.1.len().cmp(&a.1.len())));
+
+    match best {
+      Some((_, sentence, used)) => {
+        covered.extend(used.iter().cloned());
+        sentences.push(sentence.to_owned());
+      },
+      None => break,
+    }
+  }
+
+  for cell in &cells {
+    if !covered.contains(cell) && !uncovered.contains(cell) {
+      uncovered.push(cell.to_owned());
+    }
+  }
+
+  Coverage { sentences, cells, uncovered }
+}
+
+/// Construye las cadenas más cortas que pasan por una casilla de la tabla.
+struct SentenceBuilder<'builder> {
+  grammar: &'builder Grammar,
+  shortest: HashMap<String, ShortestDerivation>,
+  nullables: Vec<String>,
+  /// Cadena más corta derivada de un no terminal que empieza con un
+  /// terminal.
+  starting: HashMap<Cell, Vec<String>>,
+  /// Contexto más corto en que aparece un no terminal seguido de un
+  /// terminal (`$` si no le sigue nada): las cadenas de terminales a su
+  /// izquierda y a su derecha en alguna derivación del símbolo inicial.
+  contexts: HashMap<Cell, (Vec<String>, Vec<String>)>,
+}
+
+impl<'builder> SentenceBuilder<'builder> {
+  fn new(grammar: &'builder Grammar) -> Self {
+    let mut builder = SentenceBuilder {
+      grammar,
+      shortest: grammar.find_shortest_derivations(),
+      nullables: grammar.find_nullables(),
+      starting: HashMap::new(),
+      contexts: HashMap::new(),
+    };
+
+    builder.find_starting();
+    builder.find_contexts();
+
+    builder
+  }
+
+  /// Regresa la cadena más corta que, al evaluarse, usa la producción
+  /// `index` en la casilla M[non_terminal, terminal].
+  fn sentence_for_cell(
+    &self, non_terminal: &str, terminal: &str, index: usize,
+  ) -> Option<Vec<String>> {
+    let body = self.grammar.production_body(index);
+    let mut best: Option<Vec<String>> = None;
+
+    // La producción deriva una cadena que empieza con el terminal; sirve
+    // cualquier contexto del no terminal.
+    if let Some(derived) = self.starting_with(&body, terminal) {
+      for (prefix, suffix) in self.contexts_of(non_terminal) {
+        keep_shortest(&mut best, [prefix.as_slice(), &derived, suffix].concat());
+      }
+    }
+
+    // La producción deriva en EPSILON y el terminal sigue al no terminal.
+    if self.is_nullable(&body) {
+      let key = (String::from(non_terminal), String::from(terminal));
+      if let Some((prefix, suffix)) = self.contexts.get(&key) {
+        keep_shortest(&mut best, [prefix.as_slice(), suffix].concat());
+      }
+    }
+
+    best
+  }
+
+  /// Regresa los terminales que pueden seguir a un no terminal, en el orden
+  /// de la gramática, seguidos de `$`.
+  fn lookaheads(&self) -> Vec<String> {
+    let mut lookaheads = self.grammar.terminals.to_owned();
+    lookaheads.push(String::from("$"));
+
+    lookaheads
+  }
+
+  /// Regresa los contextos conocidos de un no terminal, en el orden de los
+  /// terminales que le siguen.
+  fn contexts_of(&self, non_terminal: &str) -> Vec<&(Vec<String>, Vec<String>)> {
+    self.lookaheads()
+      .into_iter()
+      .filter_map(|lookahead| {
+        self.contexts.get(&(String::from(non_terminal), lookahead))
+      })
+      .collect()
+  }
+
+  /// Regresa la cadena más corta derivada de una secuencia de símbolos, o
+  /// `None` si alguno no deriva ninguna cadena de terminales.
+  fn shortest_of(&self, symbols: &[String]) -> Option<Vec<String>> {
+    let mut sentence = vec![];
+
+    for symbol in symbols {
+      if self.grammar.non_terminals.contains(symbol) {
+        sentence.extend(self.shortest.get(symbol)?.sentence.to_owned());
+      } else {
+        sentence.push(String::from(symbol));
+      }
+    }
+
+    Some(sentence)
+  }
+
+  /// Regresa `true` si todos los símbolos de la secuencia pueden derivar en
+  /// EPSILON.
+  fn is_nullable(&self, symbols: &[String]) -> bool {
+    symbols.iter().all(|symbol| self.nullables.contains(symbol))
+  }
+
+  /// Regresa la cadena más corta derivada de una secuencia de símbolos que
+  /// empieza con el terminal dado.
+  fn starting_with(&self, symbols: &[String], terminal: &str) -> Option<Vec<String>> {
+    let mut best = None;
+
+    for (position, symbol) in symbols.iter().enumerate() {
+      // Los símbolos anteriores derivan en EPSILON.
+      let rest = match self.shortest_of(&symbols[position + 1..]) {
+        Some(rest) => rest,
+        None => break,
+      };
+
+      let head = if self.grammar.non_terminals.contains(symbol) {
+        self.starting.get(&(symbol.to_owned(), String::from(terminal))).cloned()
+      } else if symbol == terminal {
+        Some(vec![String::from(terminal)])
+      } else {
+        None
+      };
+
+      if let Some(head) = head {
+        keep_shortest(&mut best, [head, rest].concat());
+      }
+
+      if !self.nullables.contains(symbol) {
+        break;
+      }
+    }
+
+    best
+  }
+
+  /// Calcula `starting` repitiendo hasta que ninguna cadena se vuelva más
+  /// corta.
+  fn find_starting(&mut self) {
+    let mut changed = true;
+
+    while changed {
+      changed = false;
+
+      for (index, left) in self.grammar.sides.left.iter().enumerate() {
+        let body = self.grammar.production_body(index);
+
+        for terminal in &self.grammar.terminals {
+          if let Some(sentence) = self.starting_with(&body, terminal) {
+            let key = (left.to_owned(), terminal.to_owned());
+            let is_shorter = match self.starting.get(&key) {
+              Some(current) => sentence.len() < current.len(),
+              None => true,
+            };
+
+            if is_shorter {
+              self.starting.insert(key, sentence);
+              changed = true;
+            }
+          }
+        }
+      }
+    }
+  }
+
+  /// Calcula `contexts` a partir del símbolo inicial, repitiendo hasta que
+  /// ningún contexto se vuelva más corto.
+  fn find_contexts(&mut self) {
+    let start = match self.grammar.non_terminals.first() {
+      Some(start) => start.to_owned(),
+      None => return,
+    };
+    self.contexts.insert((start, String::from("$")), (vec![], vec![]));
+
+    let mut changed = true;
+    while changed {
+      changed = false;
+
+      for (index, left) in self.grammar.sides.left.iter().enumerate() {
+        let body = self.grammar.production_body(index);
+        let known: Vec<(String, Vec<String>, Vec<String>)> = self
+          .lookaheads()
+          .into_iter()
+          .filter_map(|lookahead| {
+            let (prefix, suffix) = self.contexts.get(&(left.to_owned(), lookahead.to_owned()))?;
+            Some((lookahead, prefix.to_owned(), suffix.to_owned()))
+          })
+          .collect();
+
+        for (lookahead, prefix, suffix) in known {
+          for (position, symbol) in body.iter().enumerate() {
+            if !self.grammar.non_terminals.contains(symbol) {
+              continue;
+            }
+
+            let before = match self.shortest_of(&body[..position]) {
+              Some(before) => [prefix.as_slice(), &before].concat(),
+              None => break,
+            };
+            let after = &body[position + 1..];
+            let mut updates = vec![];
+
+            for terminal in &self.grammar.terminals {
+              if let Some(derived) = self.starting_with(after, terminal) {
+                updates.push((
+                  terminal.to_owned(), [derived.as_slice(), &suffix].concat(),
+                ));
+              }
+            }
+
+            if self.is_nullable(after) {
+              updates.push((lookahead.to_owned(), suffix.to_owned()));
+            }
+
+            for (terminal, new_suffix) in updates {
+              let key = (symbol.to_owned(), terminal);
+              let length = before.len() + new_suffix.len();
+              let is_shorter = match self.contexts.get(&key) {
+                Some((current_prefix, current_suffix)) => {
+                  length < current_prefix.len() + current_suffix.len()
+                },
+                None => true,
+              };
+
+              if is_shorter {
+                self.contexts.insert(key, (before.to_owned(), new_suffix));
+                changed = true;
+              }
+            }
+          }
+        }
+      }
+    }
+  }
+}
+
+/// Guarda la cadena si es más corta que la mejor encontrada.
+fn keep_shortest(best: &mut Option<Vec<String>>, sentence: Vec<String>) {
+  let is_shorter = match best {
+    Some(current) => sentence.len() < current.len(),
+    None => true,
+  };
+
+  if is_shorter {
+    *best = Some(sentence);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::analyzer::LL1Analyzer;
+  use crate::examples;
+  use super::find_coverage;
+
+  fn coverage(number: usize) -> (Vec<String>, usize, usize) {
+    let mut grammar = examples::grammar(number);
+    let mut analyzer = LL1Analyzer::new(&mut grammar);
+    analyzer.build_table();
+    let coverage = find_coverage(&mut analyzer);
+
+    for sentence in &coverage.sentences {
+      assert!(analyzer.eval(&sentence.join(" ")), "{:?}", sentence);
+    }
+
+    (
+      coverage.sentences.iter().map(|sentence| sentence.join(" ")).collect(),
+      coverage.cells.len(),
+      coverage.uncovered.len(),
+    )
+  }
+
+  #[test]
+  fn cover_every_cell_of_examples() {
+    assert_eq!(coverage(1), (vec!["( id )".to_owned(), "id + id".to_owned(), "id * id".to_owned()], 13, 0));
+    assert_eq!(coverage(2), (vec!["( a )".to_owned(), "b".to_owned(), "a".to_owned()], 8, 0));
+    assert_eq!(coverage(7), (vec!["a".to_owned(), "d a".to_owned(), "b a".to_owned()], 11, 0));
+  }
+
+  #[test]
+  fn cell_of_unreachable_non_terminal_is_uncovered() {
+    let mut grammar = examples::grammar_from(&["S -> a", "X -> b"]);
+    let mut analyzer = LL1Analyzer::new(&mut grammar);
+    analyzer.build_table();
+    let coverage = find_coverage(&mut analyzer);
+
+    assert_eq!(coverage.sentences, vec![vec!["a".to_owned()]]);
+    assert_eq!(coverage.uncovered, vec![("X".to_owned(), "b".to_owned())]);
+  }
+}
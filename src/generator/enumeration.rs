@@ -0,0 +1,130 @@
+use std::collections::{HashSet, VecDeque};
+use crate::grammar::{Grammar};
+use crate::transform::{without_nullables};
+
+/// Regresa todas las cadenas de terminales de a lo más `max_length`
+/// elementos que se derivan del símbolo inicial, ordenadas por longitud y
+/// después alfabéticamente, para que dos gramáticas con el mismo lenguaje
+/// produzcan la misma lista aunque declaren sus terminales en otro orden.
+///
+/// Se recorren a lo ancho las formas sentenciales, sustituyendo siempre el
+/// no terminal de más a la izquierda. Para que el recorrido termine, los
+/// símbolos que derivan en EPSILON se eliminan al aplicar cada producción
+/// (en lugar de usar las producciones EPSILON), así cada símbolo de una
+/// forma sentencial produce al menos un terminal. Se descartan las formas
+/// cuya cadena más corta posible ya tiene más de `max_length` terminales.
+pub fn enumerate_language(grammar: &Grammar, max_length: usize) -> Vec<Vec<String>> {
+  let shortest = grammar.find_shortest_derivations();
+  let nullables = grammar.find_nullables();
+  let mut sentences: Vec<Vec<String>> = vec![];
+
+  let start = match grammar.non_terminals.first() {
+    Some(start) if shortest.contains_key(start) => start.to_owned(),
+    _ => return sentences,
+  };
+
+  if nullables.contains(&start) {
+    sentences.push(vec![]);
+  }
+
+  // Longitud mínima (sin contar EPSILON) de lo que deriva un símbolo.
+  let min_yield = |symbol: &String| -> usize {
+    match shortest.get(symbol) {
+      Some(derivation) => derivation.sentence.len().max(1),
+      None => 1,
+    }
+  };
+
+  // Variantes de cada producción sin los símbolos que derivan en EPSILON.
+  let mut variants: Vec<Vec<Vec<String>>> = vec![];
+  for index in 0..grammar.sides.left.len() {
+    let body = grammar.production_body(index);
+    let productive = body.iter().all(|symbol| {
+      !grammar.non_terminals.contains(symbol) || shortest.contains_key(symbol)
+    });
+
+    variants.push(if productive {
+      without_nullables(&body, &nullables)
+    } else {
+      vec![]
+    });
+  }
+
+  let mut visited: HashSet<Vec<String>> = HashSet::new();
+  let mut found: HashSet<Vec<String>> = HashSet::new();
+  let mut queue = VecDeque::new();
+  visited.insert(vec![start.to_owned()]);
+  queue.push_back(vec![start]);
+
+  while let Some(form) = queue.pop_front() {
+    let position = match form
+      .iter()
+      .position(|symbol| grammar.non_terminals.contains(symbol)) {
+      Some(position) => position,
+      None => {
+        if found.insert(form.to_owned()) {
+          sentences.push(form);
+        }
+        continue;
+      },
+    };
+
+    for index in grammar.get_indexes_in_non_terminals(&form[position]) {
+      for variant in &variants[index] {
+        let next = [&form[..position], variant.as_slice(), &form[position + 1..]].concat();
+        let length: usize = next.iter().map(&min_yield).sum();
+
+        if length <= max_length && visited.insert(next.to_owned()) {
+          queue.push_back(next);
+        }
+      }
+    }
+  }
+
+  sentences.sort_by(|a, b| a.len().cmp(&b.len()).then(a.cmp(b)));
+  sentences
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::examples;
+  use super::enumerate_language;
+
+  fn language(grammar: &crate::grammar::Grammar, max_length: usize) -> Vec<String> {
+    enumerate_language(grammar, max_length)
+      .iter()
+      .map(|sentence| sentence.join(" "))
+      .collect()
+  }
+
+  #[test]
+  fn sentences_sorted_by_length() {
+    assert_eq!(language(&examples::grammar(2), 3), vec!["a", "b", "( a )", "( b )"]);
+  }
+
+  #[test]
+  fn left_recursive_grammar() {
+    assert_eq!(language(&examples::grammar(3), 3), vec!["id", "( id )", "id * id", "id + id"]);
+  }
+
+  #[test]
+  fn epsilon_productions() {
+    assert_eq!(language(&examples::grammar(7), 3), vec!["a", "b a", "d a", "b d a"]);
+
+    let grammar = examples::grammar_from(&["S -> a S b | ' '"]);
+    assert_eq!(language(&grammar, 4), vec!["", "a b", "a a b b"]);
+  }
+
+  #[test]
+  fn unproductive_start_has_no_sentences() {
+    assert!(language(&examples::grammar_from(&["S -> a S"]), 5).is_empty());
+  }
+
+  #[test]
+  fn count_sentences_of_example() {
+    let sentences = enumerate_language(&examples::grammar(1), 5);
+
+    assert_eq!(sentences.len(), 15);
+    assert!(sentences.iter().all(|sentence| sentence.len() <= 5));
+  }
+}
@@ -0,0 +1,125 @@
+use crate::grammar::{Grammar};
+use super::{Generator, enumerate_language};
+
+/// Cadena que pertenece al lenguaje de solo una de dos gramáticas.
+pub struct Difference {
+  /// Elementos de la cadena.
+  pub sentence: Vec<String>,
+  /// `true` si la genera la primera gramática, `false` si la segunda.
+  pub in_first: bool,
+}
+
+/// Resultado de comparar los lenguajes de dos gramáticas.
+pub struct Comparison {
+  /// Cantidad de cadenas de a lo más la longitud límite que generan ambas.
+  pub enumerated: usize,
+  /// Cantidad de cadenas al azar revisadas en la otra gramática.
+  pub sampled: usize,
+  /// Cadena más corta encontrada que distingue a las gramáticas.
+  pub difference: Option<Difference>,
+}
+
+/// Compara los lenguajes de dos gramáticas.
+///
+/// Primero se enumeran todas las cadenas de a lo más `max_length`
+/// terminales de cada una. Si coinciden, se generan `samples` cadenas al
+/// azar de cada gramática (de a lo más `sample_length` terminales) y se
+/// revisa si la otra también las deriva. Como la comparación es acotada,
+/// que no se encuentre una diferencia no prueba que los lenguajes sean
+/// iguales.
+pub fn compare_languages(
+  first: &Grammar,
+  second: &Grammar,
+  max_length: usize,
+  samples: usize,
+  sample_length: usize,
+  seed: u64,
+) -> Comparison {
+  let first_sentences = enumerate_language(first, max_length);
+  let second_sentences = enumerate_language(second, max_length);
+  let mut differences = vec![];
+
+  for sentence in &first_sentences {
+    if second_sentences.binary_search_by(|other| compare(other, sentence)).is_err() {
+      differences.push(Difference { sentence: sentence.to_owned(), in_first: true });
+    }
+  }
+  for sentence in &second_sentences {
+    if first_sentences.binary_search_by(|other| compare(other, sentence)).is_err() {
+      differences.push(Difference { sentence: sentence.to_owned(), in_first: false });
+    }
+  }
+
+  let mut sampled = 0;
+  if differences.is_empty() {
+    for (index, &(grammar, other)) in [(first, second), (second, first)].iter().enumerate() {
+      let mut generator = Generator::new(grammar, seed.wrapping_add(index as u64));
+      generator.max_length = sample_length;
+
+      for _ in 0..samples {
+        let sentence = match generator.sentence() {
+          Some(sentence) => sentence,
+          None => break,
+        };
+        sampled += 1;
+
+        if !other.derives(&sentence) {
+          differences.push(Difference { sentence, in_first: index == 0 });
+        }
+      }
+    }
+  }
+
+  Comparison {
+    enumerated: first_sentences.len().min(second_sentences.len()),
+    sampled,
+    difference: differences
+      .into_iter()
+      .min_by(|a, b| compare(&a.sentence, &b.sentence)),
+  }
+}
+
+/// Compara dos cadenas por longitud y después alfabéticamente, el mismo
+/// orden que usa `enumerate_language`.
+fn compare(a: &[String], b: &[String]) -> std::cmp::Ordering {
+  a.len().cmp(&b.len()).then(a.cmp(b))
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::examples;
+  use super::compare_languages;
+
+  #[test]
+  fn grammars_with_the_same_language() {
+    // input3 es input1 con recursión por la izquierda.
+    let comparison = compare_languages(&examples::grammar(3), &examples::grammar(1), 5, 50, 15, 0);
+
+    assert!(comparison.difference.is_none());
+    assert_eq!(comparison.enumerated, 15);
+    assert_eq!(comparison.sampled, 100);
+  }
+
+  #[test]
+  fn shortest_difference() {
+    let first = examples::grammar_from(&["S -> a S | ' '"]);
+    let second = examples::grammar_from(&["S -> a a S | ' '"]);
+    let comparison = compare_languages(&first, &second, 4, 10, 10, 0);
+    let difference = comparison.difference.unwrap();
+
+    assert_eq!((difference.sentence, difference.in_first), (vec![String::from("a")], true));
+    assert_eq!(comparison.sampled, 0);
+  }
+
+  #[test]
+  fn difference_found_by_sampling() {
+    // Las gramáticas difieren solo en cadenas de más de 3 terminales.
+    let first = examples::grammar_from(&["S -> a S | ' '"]);
+    let second = examples::grammar_from(&["S -> a | a a | a a a | ' '"]);
+    let comparison = compare_languages(&first, &second, 3, 50, 20, 0);
+    let difference = comparison.difference.unwrap();
+
+    assert!(difference.in_first);
+    assert!(difference.sentence.len() > 3);
+  }
+}
@@ -0,0 +1,212 @@
+mod coverage;
+mod enumeration;
+mod equivalence;
+mod mutation;
+mod random;
+
+use std::collections::{HashMap};
+use super::grammar::{Grammar, ShortestDerivation};
+
+pub use coverage::find_coverage;
+pub use enumeration::enumerate_language;
+pub use equivalence::compare_languages;
+pub use mutation::{MutationKind, find_mutants};
+pub use random::Random;
+
+/// Estructura que genera cadenas al azar a partir del símbolo inicial de una
+/// gramática.
+pub struct Generator<'generator> {
+  grammar: &'generator Grammar,
+  /// Derivaciones más cortas de cada no terminal productivo.
+  shortest: HashMap<String, ShortestDerivation>,
+  random: Random,
+  /// Profundidad máxima del árbol de derivación. Al llegar a ella, cada no
+  /// terminal se sustituye por su derivación más corta.
+  pub max_depth: usize,
+  /// Cantidad máxima de terminales en cada cadena generada.
+  pub max_length: usize,
+  /// Peso de cada producción (por índice) al elegir una al azar. Una
+  /// producción con peso 2 se elige el doble de veces que una con peso 1.
+  pub weights: Vec<f64>,
+}
+
+impl<'generator> Generator<'generator> {
+  /// Crea un generador para la gramática con la semilla dada. Todas las
+  /// producciones empiezan con peso 1.
+  pub fn new(grammar: &'generator Grammar, seed: u64) -> Self {
+    Generator {
+      grammar,
+      shortest: grammar.find_shortest_derivations(),
+      random: Random::new(seed),
+      max_depth: 10,
+      max_length: 20,
+      weights: vec![1.0; grammar.sides.left.len()],
+    }
+  }
+
+  /// Genera una cadena de terminales derivada del símbolo inicial, con sus
+  /// elementos en el orden en que los recibe `LL1Analyzer::eval`.
+  ///
+  /// Regresa `None` si el símbolo inicial no deriva ninguna cadena de a lo
+  /// más `max_length` terminales.
+  pub fn sentence(&mut self) -> Option<Vec<String>> {
+    let start = self.grammar.non_terminals.first()?;
+    let mut pending = self.shortest.get(start)?.sentence.len();
+
+    if pending > self.max_length {
+      return None;
+    }
+
+    let mut sentence = vec![];
+    // Pila de símbolos por derivar (el tope es el último elemento) con su
+    // profundidad en el árbol de derivación.
+    let mut stack = vec![(String::from(start), 0)];
+
+    while let Some((symbol, depth)) = stack.pop() {
+      let shortest = match self.shortest.get(&symbol) {
+        Some(derivation) => derivation.sentence.to_owned(),
+        None => {
+          // Es un terminal.
+          sentence.push(symbol);
+          pending -= 1;
+          continue;
+        },
+      };
+
+      if depth >= self.max_depth {
+        pending -= shortest.len();
+        sentence.extend(shortest);
+        continue;
+      }
+
+      // Solo se consideran las producciones que pueden terminar sin pasar
+      // de la longitud máxima.
+      let budget = self.max_length - sentence.len() - pending + shortest.len();
+      let mut candidates = vec![];
+
+      for index in self.grammar.get_indexes_in_non_terminals(&symbol) {
+        if let Some(length) = self.min_length(index) {
+          if length <= budget {
+            candidates.push((index, length));
+          }
+        }
+      }
+
+      let (index, length) = self.choose(&candidates);
+      pending = pending - shortest.len() + length;
+
+      for el in self.grammar.production_body(index).into_iter().rev() {
+        stack.push((el, depth + 1));
+      }
+    }
+
+    Some(sentence)
+  }
+
+  /// Regresa la longitud mínima de las cadenas que deriva una producción, o
+  /// `None` si contiene no terminales que no derivan ninguna cadena.
+  fn min_length(&self, index: usize) -> Option<usize> {
+    let mut length = 0;
+
+    for el in self.grammar.production_body(index) {
+      if self.grammar.non_terminals.contains(&el) {
+        length += self.shortest.get(&el)?.sentence.len();
+      } else {
+        length += 1;
+      }
+    }
+
+    Some(length)
+  }
+
+  /// Elige al azar una producción de acuerdo a su peso. Si ninguna tiene
+  /// peso positivo, todas tienen la misma probabilidad.
+  fn choose(&mut self, candidates: &[(usize, usize)]) -> (usize, usize) {
+    let total: f64 = candidates
+      .iter()
+      .map(|(index, _)| self.weights[*index].max(0.0))
+      .sum();
+
+    if total <= 0.0 {
+      return candidates[self.random.below(candidates.len())];
+    }
+
+    let mut target = self.random.next_f64() * total;
+    for candidate in candidates {
+      let weight = self.weights[candidate.0].max(0.0);
+      if target < weight {
+        return *candidate;
+      }
+      target -= weight;
+    }
+
+    // Por errores de redondeo se puede llegar al final de la lista.
+    *candidates.iter().rev().find(|(index, _)| self.weights[*index] > 0.0).unwrap()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::analyzer::LL1Analyzer;
+  use crate::examples;
+  use super::Generator;
+
+  fn sentences(generator: &mut Generator, count: usize) -> Vec<Vec<String>> {
+    (0..count).map(|_| generator.sentence().unwrap()).collect()
+  }
+
+  #[test]
+  fn same_seed_same_sentences() {
+    let grammar = examples::grammar(1);
+    let first = sentences(&mut Generator::new(&grammar, 7), 20);
+    let second = sentences(&mut Generator::new(&grammar, 7), 20);
+
+    assert_eq!(first, second);
+    assert_ne!(first, sentences(&mut Generator::new(&grammar, 8), 20));
+  }
+
+  #[test]
+  fn sentences_belong_to_the_language() {
+    let mut grammar = examples::grammar(1);
+    let generated = {
+      let mut generator = Generator::new(&grammar, 1);
+      generator.max_length = 9;
+      sentences(&mut generator, 50)
+    };
+    let mut analyzer = LL1Analyzer::new(&mut grammar);
+    analyzer.build_table();
+
+    for sentence in generated {
+      assert!(sentence.len() <= 9, "{:?}", sentence);
+      assert!(analyzer.eval(&sentence.join(" ")), "{:?}", sentence);
+    }
+  }
+
+  #[test]
+  fn production_with_weight_zero_is_never_chosen() {
+    // La producción 1 es `A -> ( A )`.
+    let grammar = examples::grammar(2);
+    let mut generator = Generator::new(&grammar, 3);
+    generator.weights[1] = 0.0;
+
+    for sentence in sentences(&mut generator, 20) {
+      assert_eq!(sentence.len(), 1);
+    }
+  }
+
+  #[test]
+  fn no_sentence_if_the_shortest_is_too_long() {
+    let grammar = examples::grammar_from(&["S -> a a S | a a a"]);
+    let mut generator = Generator::new(&grammar, 0);
+    generator.max_length = 2;
+
+    assert_eq!(generator.sentence(), None);
+  }
+
+  #[test]
+  fn no_sentence_if_start_is_unproductive() {
+    let grammar = examples::grammar_from(&["S -> a S"]);
+
+    assert_eq!(Generator::new(&grammar, 0).sentence(), None);
+  }
+}
@@ -0,0 +1,171 @@
+use crate::analyzer::{LL1Analyzer};
+use super::random::{Random};
+
+/// Tipo de cambio aplicado a una cadena válida.
+#[derive(Clone, Copy)]
+pub enum MutationKind {
+  /// Se agrega un terminal.
+  Insertion,
+  /// Se elimina un elemento.
+  Deletion,
+  /// Se reemplaza un elemento por otro terminal.
+  Substitution,
+}
+
+/// Cadena inválida obtenida al cambiar una cadena válida.
+pub struct Mutant {
+  /// Elementos de la cadena inválida.
+  pub sentence: Vec<String>,
+  /// Cambio que se aplicó.
+  pub kind: MutationKind,
+  /// Posición (empezando en 1) donde el parser encuentra el error.
+  pub error_position: usize,
+}
+
+/// Genera hasta `count` cadenas inválidas distintas a partir de las cadenas
+/// válidas dadas, insertando, eliminando o reemplazando un terminal. Cada
+/// cadena se evalúa con el analizador y solo se conservan las rechazadas,
+/// junto con la posición en la que se encuentra el error.
+pub fn find_mutants(
+  analyzer: &mut LL1Analyzer,
+  sentences: &[Vec<String>],
+  random: &mut Random,
+  count: usize,
+) -> Vec<Mutant> {
+  let terminals = analyzer.grammar.terminals.to_owned();
+  let mut mutants: Vec<Mutant> = vec![];
+
+  if sentences.is_empty() || terminals.is_empty() {
+    return mutants;
+  }
+
+  // Se limita la cantidad de intentos porque en algunas gramáticas casi
+  // todos los cambios producen cadenas válidas.
+  for _ in 0..count * 20 {
+    if mutants.len() >= count {
+      break;
+    }
+
+    let original = &sentences[random.below(sentences.len())];
+    let kind = match random.below(3) {
+      0 => MutationKind::Insertion,
+      1 => MutationKind::Deletion,
+      _ => MutationKind::Substitution,
+    };
+    let sentence = match mutate(original, kind, &terminals, random) {
+      Some(sentence) => sentence,
+      None => continue,
+    };
+
+    if sentences.contains(&sentence)
+      || mutants.iter().any(|mutant| mutant.sentence == sentence) {
+      continue;
+    }
+
+    let trace = analyzer.trace(&sentence.join(" "));
+    if let (false, Some(error_position)) = (trace.accepted, trace.error_position) {
+      mutants.push(Mutant { sentence, kind, error_position });
+    }
+  }
+
+  mutants
+}
+
+/// Aplica un cambio al azar del tipo dado. Regresa `None` si el cambio no se
+/// puede aplicar o no modifica la cadena.
+fn mutate(
+  sentence: &[String],
+  kind: MutationKind,
+  terminals: &[String],
+  random: &mut Random,
+) -> Option<Vec<String>> {
+  let mut mutated = sentence.to_owned();
+  let terminal = terminals[random.below(terminals.len())].to_owned();
+
+  match kind {
+    MutationKind::Insertion => {
+      let position = random.below(sentence.len() + 1);
+      mutated.insert(position, terminal);
+    },
+    MutationKind::Deletion => {
+      if sentence.is_empty() {
+        return None;
+      }
+      mutated.remove(random.below(sentence.len()));
+    },
+    MutationKind::Substitution => {
+      if sentence.is_empty() {
+        return None;
+      }
+      let position = random.below(sentence.len());
+      if mutated[position] == terminal {
+        return None;
+      }
+      mutated[position] = terminal;
+    },
+  }
+
+  Some(mutated)
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::analyzer::LL1Analyzer;
+  use crate::examples;
+  use crate::generator::Random;
+  use super::{MutationKind, find_mutants, mutate};
+
+  fn sentence(text: &str) -> Vec<String> {
+    text.split_whitespace().map(String::from).collect()
+  }
+
+  #[test]
+  fn mutants_are_rejected_at_their_error_position() {
+    let mut grammar = examples::grammar(1);
+    let mut analyzer = LL1Analyzer::new(&mut grammar);
+    analyzer.build_table();
+    let sentences = vec![sentence("id + id"), sentence("( id * id )")];
+    let mutants = find_mutants(&mut analyzer, &sentences, &mut Random::new(9), 10);
+
+    assert_eq!(mutants.len(), 10);
+    for (index, mutant) in mutants.iter().enumerate() {
+      let text = mutant.sentence.join(" ");
+      let trace = analyzer.trace(&text);
+
+      assert!(!trace.accepted, "{}", text);
+      assert_eq!(trace.error_position, Some(mutant.error_position), "{}", text);
+      assert!(mutants[..index].iter().all(|other| other.sentence != mutant.sentence));
+    }
+  }
+
+  #[test]
+  fn mutate_changes_one_element() {
+    let original = sentence("id + id");
+    let terminals = sentence("+ * ( ) id");
+    let mut random = Random::new(4);
+
+    for _ in 0..50 {
+      if let Some(mutated) = mutate(&original, MutationKind::Insertion, &terminals, &mut random) {
+        assert_eq!(mutated.len(), 4);
+      }
+      if let Some(mutated) = mutate(&original, MutationKind::Deletion, &terminals, &mut random) {
+        assert_eq!(mutated.len(), 2);
+      }
+      if let Some(mutated) = mutate(&original, MutationKind::Substitution, &terminals, &mut random) {
+        let changes = original.iter().zip(&mutated).filter(|(a, b)| a != b).count();
+        assert_eq!((mutated.len(), changes), (3, 1));
+      }
+    }
+
+    assert_eq!(mutate(&[], MutationKind::Deletion, &terminals, &mut random), None);
+  }
+
+  #[test]
+  fn no_mutants_without_sentences() {
+    let mut grammar = examples::grammar(1);
+    let mut analyzer = LL1Analyzer::new(&mut grammar);
+    analyzer.build_table();
+
+    assert!(find_mutants(&mut analyzer, &[], &mut Random::new(0), 5).is_empty());
+  }
+}
@@ -0,0 +1,58 @@
+/// Generador de números pseudoaleatorios (SplitMix64). Con la misma semilla
+/// siempre produce la misma secuencia, lo que permite reproducir las cadenas
+/// generadas.
+pub struct Random {
+  state: u64,
+}
+
+impl Random {
+  /// Crea un generador a partir de una semilla.
+  pub fn new(seed: u64) -> Self {
+    Random { state: seed }
+  }
+
+  /// Regresa el siguiente número de 64 bits de la secuencia.
+  pub fn next_u64(&mut self) -> u64 {
+    self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = self.state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+  }
+
+  /// Regresa un número en el intervalo `[0, 1)`.
+  pub fn next_f64(&mut self) -> f64 {
+    (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+  }
+
+  /// Regresa un número en el intervalo `[0, bound)`.
+  pub fn below(&mut self, bound: usize) -> usize {
+    (self.next_u64() % bound as u64) as usize
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::Random;
+
+  #[test]
+  fn same_seed_same_sequence() {
+    let mut first = Random::new(42);
+    let mut second = Random::new(42);
+
+    for _ in 0..10 {
+      assert_eq!(first.next_u64(), second.next_u64());
+    }
+  }
+
+  #[test]
+  fn numbers_in_range() {
+    let mut random = Random::new(5);
+
+    for _ in 0..1000 {
+      let number = random.next_f64();
+      assert!((0.0..1.0).contains(&number));
+      assert!(random.below(3) < 3);
+    }
+  }
+}
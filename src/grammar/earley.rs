@@ -0,0 +1,119 @@
+use std::collections::{HashSet};
+use super::Grammar;
+
+/// Producción parcialmente reconocida: índice de la producción, cantidad de
+/// símbolos ya reconocidos y posición de la cadena donde empezó.
+type Item = (usize, usize, usize);
+
+impl Grammar {
+  /// Regresa `true` si la cadena de terminales se deriva del símbolo
+  /// inicial.
+  ///
+  /// A diferencia de `LL1Analyzer::eval`, funciona con cualquier gramática
+  /// libre de contexto (ambigua, recursiva por la izquierda, etc.) porque
+  /// usa el algoritmo de Earley. Los no terminales que derivan en EPSILON se
+  /// saltan al predecirlos, como proponen Aycock y Horspool.
+  pub fn derives(&self, sentence: &[String]) -> bool {
+    let start = match self.non_terminals.first() {
+      Some(start) => start,
+      None => return false,
+    };
+    let bodies: Vec<Vec<String>> = (0..self.sides.left.len())
+      .map(|index| self.production_body(index))
+      .collect();
+    let nullables = self.find_nullables();
+
+    let mut sets: Vec<Vec<Item>> = vec![vec![]; sentence.len() + 1];
+    let mut seen: Vec<HashSet<Item>> = vec![HashSet::new(); sentence.len() + 1];
+    let mut add = |sets: &mut Vec<Vec<Item>>, position: usize, item: Item| {
+      if seen[position].insert(item) {
+        sets[position].push(item);
+      }
+    };
+
+    for index in self.get_indexes_in_non_terminals(start) {
+      add(&mut sets, 0, (index, 0, 0));
+    }
+
+    for position in 0..=sentence.len() {
+      let mut current = 0;
+
+      while current < sets[position].len() {
+        let (index, dot, origin) = sets[position][current];
+        current += 1;
+
+        match bodies[index].get(dot) {
+          // Predicción
+          Some(symbol) if self.non_terminals.contains(symbol) => {
+            for next in self.get_indexes_in_non_terminals(symbol) {
+              add(&mut sets, position, (next, 0, position));
+            }
+            if nullables.contains(symbol) {
+              add(&mut sets, position, (index, dot + 1, origin));
+            }
+          },
+          // Lectura
+          Some(symbol) => {
+            if sentence.get(position) == Some(symbol) {
+              add(&mut sets, position + 1, (index, dot + 1, origin));
+            }
+          },
+          // Terminación
+          None => {
+            let left = &self.sides.left[index];
+            let waiting: Vec<Item> = sets[origin]
+              .iter()
+              .filter(|(other, other_dot, _)| bodies[*other].get(*other_dot) == Some(left))
+              .cloned()
+              .collect();
+
+            for (other, other_dot, other_origin) in waiting {
+              add(&mut sets, position, (other, other_dot + 1, other_origin));
+            }
+          },
+        }
+      }
+    }
+
+    sets[sentence.len()].iter().any(|(index, dot, origin)| {
+      *origin == 0 && *dot == bodies[*index].len() && self.sides.left[*index] == *start
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::examples;
+
+  fn derives(grammar: &super::Grammar, text: &str) -> bool {
+    let sentence: Vec<String> = text.split_whitespace().map(String::from).collect();
+    grammar.derives(&sentence)
+  }
+
+  #[test]
+  fn left_recursive_grammar() {
+    let grammar = examples::grammar(3);
+
+    assert!(derives(&grammar, "id + id * ( id + id )"));
+    assert!(!derives(&grammar, "id + * id"));
+    assert!(!derives(&grammar, ""));
+  }
+
+  #[test]
+  fn ambiguous_grammar_with_epsilon() {
+    let grammar = examples::grammar_from(&["S -> S S | ( S ) | ' '"]);
+
+    assert!(derives(&grammar, ""));
+    assert!(derives(&grammar, "( ) ( ( ) )"));
+    assert!(!derives(&grammar, "( ) )"));
+  }
+
+  #[test]
+  fn nullable_non_terminals_are_skipped() {
+    let grammar = examples::grammar(7);
+
+    assert!(derives(&grammar, "a"));
+    assert!(derives(&grammar, "b d a"));
+    assert!(!derives(&grammar, "d b a"));
+  }
+}
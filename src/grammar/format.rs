@@ -0,0 +1,102 @@
+use super::Grammar;
+
+impl Grammar {
+  /// Regresa las producciones en forma canónica, como líneas de un archivo
+  /// de entrada: agrupadas por no terminal empezando por el símbolo inicial,
+  /// con `->` y `|` alineados y EPSILON escrito como `' '`.
+  ///
+  /// ```txt
+  /// E      -> T EPrime
+  /// EPrime -> + T EPrime
+  ///        |  ' '
+  /// ```
+  ///
+  /// Si `sort` es `true`, los demás no terminales se ordenan alfabéticamente
+  /// y las producciones de cada uno también, con EPSILON al final. Ninguna
+  /// opción cambia las producciones de la gramática.
+  pub fn formatted_lines(&self, sort: bool) -> Vec<String> {
+    let mut non_terminals = self.non_terminals.to_owned();
+    if sort && non_terminals.len() > 1 {
+      non_terminals[1..].sort();
+    }
+
+    let width = non_terminals
+      .iter()
+      .map(|non_terminal| non_terminal.chars().count())
+      .max()
+      .unwrap_or(0);
+    let mut lines = vec![];
+
+    for non_terminal in &non_terminals {
+      let mut bodies: Vec<Vec<String>> = self
+        .get_indexes_in_non_terminals(non_terminal)
+        .into_iter()
+        .map(|index| self.production_body(index))
+        .collect();
+
+      if sort {
+        bodies.sort_by(|a, b| a.is_empty().cmp(&b.is_empty()).then(a.cmp(b)));
+      }
+
+      for (position, body) in bodies.iter().enumerate() {
+        let body = if body.is_empty() {
+          String::from("' '")
+        } else {
+          body.join(" ")
+        };
+
+        lines.push(match position {
+          0 => format!("{:width$} -> {}", non_terminal, body, width = width),
+          _ => format!("{:width$} |  {}", "", body, width = width),
+        });
+      }
+    }
+
+    lines
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::examples;
+
+  #[test]
+  fn format_example() {
+    let grammar = examples::unanalyzed(&examples::productions(1));
+
+    assert_eq!(grammar.formatted_lines(false), vec![
+      "E      -> T EPrime",
+      "EPrime -> + T EPrime",
+      "       |  ' '",
+      "T      -> F TPrime",
+      "TPrime -> * F TPrime",
+      "       |  ' '",
+      "F      -> ( E )",
+      "       |  id",
+    ]);
+  }
+
+  #[test]
+  fn sort_non_terminals_and_productions() {
+    let grammar = examples::unanalyzed(&["S -> B | A", "B -> b | ' ' | a", "A -> a"]);
+
+    assert_eq!(grammar.formatted_lines(true), vec![
+      "S -> A",
+      "  |  B",
+      "A -> a",
+      "B -> a",
+      "  |  b",
+      "  |  ' '",
+    ]);
+  }
+
+  #[test]
+  fn formatted_lines_read_as_the_same_grammar() {
+    for number in 1..=7 {
+      let grammar = examples::unanalyzed(&examples::productions(number));
+      let formatted = examples::unanalyzed(&grammar.formatted_lines(false));
+
+      assert_eq!(formatted.production_lines(), grammar.production_lines(), "input{}", number);
+    }
+  }
+}
@@ -1,3 +1,6 @@
+mod earley;
+mod format;
+
 use std::collections::{HashMap, HashSet};
 use itertools::Itertools;
 
@@ -8,6 +11,15 @@ pub struct Sides {
   pub left: Vec<String>,
   /// Lado derecho de el símbolo `->`. Contiene terminales y no terminales.
   pub right: Vec<String>,
+  /// Línea de cada producción, contando desde 0 entre las líneas de
+  /// producciones. Varias producciones pueden estar en la misma línea.
+  pub lines: Vec<usize>,
+}
+
+/// Derivación más corta de un no terminal a una cadena de terminales.
+pub struct ShortestDerivation {
+  /// Cadena de terminales más corta que deriva el no terminal.
+  pub sentence: Vec<String>,
 }
 
 pub struct Grammar {
@@ -22,25 +34,104 @@ pub struct Grammar {
 impl Grammar {
   /// Transforma un vector de producciones en dos vectores, uno para el lado
   /// derecho y otro para el izquierdo.
+  ///
+  /// Una línea puede tener varias producciones separadas por `|`
+  /// (`A -> a | b`), y una línea que empieza con `|` agrega otra producción
+  /// al no terminal de la línea anterior. EPSILON se puede escribir como
+  /// `' '` o como `ε`.
   /// 
   /// Regresa una nueva gramática (Grammar) con los lados divididos.
+  ///
+  /// # Panics
+  ///
+  /// Si una línea no es una producción `A -> α` o empieza con `|` sin una
+  /// producción anterior. `Grammar::try_new` regresa el error en su lugar.
   pub fn new(productions: Vec<String>) -> Self {
-    let mut left = vec![];
-    let mut right = vec![];
+    Grammar::try_new(productions).unwrap_or_else(|e| panic!("{}", e))
+  }
 
-    for production in productions {
-        let splited = production.split(" -> ").collect::<Vec<_>>();
-        left.push(String::from(splited[0]));
-        right.push(String::from(splited[1]));
+  /// Igual que `Grammar::new`, pero regresa un error si una línea no es una
+  /// producción `A -> α` o empieza con `|` sin una producción anterior.
+  pub fn try_new(productions: Vec<String>) -> Result<Self, String> {
+    let mut left: Vec<String> = vec![];
+    let mut right = vec![];
+    let mut lines = vec![];
+
+    for (line, production) in productions.iter().enumerate() {
+        let (non_terminal, alternatives) = match production.trim().strip_prefix('|') {
+          Some(rest) => match left.last() {
+            Some(previous) => (previous.to_owned(), rest),
+            None => {
+              return Err(format!("`{}`: `|` sin una producción anterior", production.trim()));
+            },
+          },
+          None => match production.split_once(" -> ") {
+            Some((non_terminal, rest))
+              if !non_terminal.trim().is_empty() && !non_terminal.trim().contains(char::is_whitespace) =>
+            {
+              (String::from(non_terminal.trim()), rest)
+            },
+            _ => {
+              return Err(format!(
+                "se esperaba una producción `A -> α`, se encontró `{}`", production.trim(),
+              ));
+            },
+          },
+        };
+
+        let elements: Vec<&str> = alternatives.split_whitespace().collect();
+        for alternative in elements.split(|el| *el == "|") {
+          let body = match alternative {
+            [] | ["ε"] => String::from("' '"),
+            _ => alternative.join(" "),
+          };
+
+          left.push(non_terminal.to_owned());
+          right.push(body);
+          lines.push(line);
+        }
     }
 
-    Grammar {
+    Ok(Grammar {
       terminals: vec![],
       non_terminals: vec![],
-      sides: Sides { left, right },
+      sides: Sides { left, right, lines },
       firsts: HashMap::new(),
       follows: HashMap::new(),
       productions: HashMap::new(),
+    })
+  }
+
+  /// Crea la gramática a partir de sus producciones y encuentra sus
+  /// terminales y no terminales. Los conjuntos FIRST y FOLLOW no se
+  /// calculan.
+  ///
+  /// # Panics
+  ///
+  /// En los mismos casos que `Grammar::new`.
+  pub fn from_productions(productions: Vec<String>) -> Self {
+    Grammar::try_from_productions(productions).unwrap_or_else(|e| panic!("{}", e))
+  }
+
+  /// Igual que `Grammar::from_productions`, pero regresa el error de
+  /// `Grammar::try_new` en lugar de entrar en pánico.
+  pub fn try_from_productions(productions: Vec<String>) -> Result<Self, String> {
+    let mut grammar = Grammar::try_new(productions)?;
+    grammar.find_non_terminals();
+    grammar.find_terminals();
+    grammar.find_all_productions();
+
+    Ok(grammar)
+  }
+
+  /// Calcula los conjuntos FIRST y FOLLOW de todos los no terminales.
+  ///
+  /// Se desborda la pila si la gramática tiene un ciclo A =>+ A (ver
+  /// `linter::lint_grammar`).
+  pub fn find_sets(&mut self) {
+    for non_terminal in self.non_terminals.clone() {
+      self.find_single_first(&non_terminal);
+      self.find_follow(&non_terminal);
     }
   }
 
@@ -93,25 +184,34 @@ impl Grammar {
   }
 
   /// Regresa FIRST de toda la producción.
+  ///
+  /// EPSILON solo forma parte del resultado si todos los elementos de la
+  /// producción pueden derivar en EPSILON.
   pub fn find_first_production(
     &mut self, elements: &Vec<String>,
   ) -> Vec<String> {
     let mut first = vec![];
+    let mut all_nullable = true;
 
     for element in elements {
       let next_first = self.find_single_first(element);
 
-      for maybe_next_first in next_first.to_owned() {
-        if !first.contains(&maybe_next_first) {
+      for maybe_next_first in next_first.iter().cloned() {
+        if maybe_next_first != "' '" && !first.contains(&maybe_next_first) {
           first.push(maybe_next_first);
         }
       }
 
       if !next_first.contains(&String::from("' '")) {
+        all_nullable = false;
         break;
       }
     }
 
+    if all_nullable {
+      first.push(String::from("' '"));
+    }
+
     first
   }
 
@@ -126,9 +226,8 @@ impl Grammar {
 
     // Revisa si el FIRST del no terminal ya fue encontrado anteriormente
     if self.firsts.contains_key(non_terminal) {
-      match self.firsts.get(non_terminal) {
-        Some(first) => return first.to_owned(),
-        None => {},
+      if let Some(first) = self.firsts.get(non_terminal) {
+        return first.to_owned();
       }
     }
 
@@ -164,44 +263,156 @@ impl Grammar {
       }
     }
 
-    // Guardar en el "caché" el FIRST del no terminal
+    // Guardar en el "caché" el FIRST del no terminal, en el orden de la
+    // gramática.
+    self.sort_symbols(&mut first);
     self.firsts.insert(String::from(non_terminal), first.to_owned());
     first
   }
 
+  /// Regresa FIRST de toda la producción usando únicamente los FIRST ya
+  /// calculados de los no terminales.
   pub fn quick_first_production(&self, elements: &Vec<String>) -> Vec<String> {
     let mut result = vec![];
 
-    let mut another = false;
-    let mut found_epsilon = false;
     for el in elements {
-      if el == "'" && !found_epsilon {
-        result.push(String::from("' '"));
-        another = true;
-        found_epsilon = true;
-      } else if el == "'" && found_epsilon {
-        found_epsilon = false;
-      }
-  
-      if self.terminals.contains(&el) {
-        result.push(String::from(el));
+      // Los elementos `'` representan EPSILON y no aportan terminales.
+      if el == "'" {
+        continue;
       }
-  
-      if self.non_terminals.contains(&el) {
-        result.append(&mut self.firsts.get(el).unwrap().to_owned());
+
+      let next_first = if self.non_terminals.contains(el) {
+        self.firsts.get(el).unwrap().to_owned()
+      } else {
+        vec![String::from(el)]
+      };
+
+      for maybe_first in next_first.iter() {
+        if maybe_first != "' '" && !result.contains(maybe_first) {
+          result.push(String::from(maybe_first));
+        }
       }
 
-      if !another && !result.contains(&String::from("' '")) {
+      if !next_first.contains(&String::from("' '")) {
         return result;
       }
     }
 
+    result.push(String::from("' '"));
     result
   }
 
+  /// Regresa los no terminales que pueden derivar en EPSILON, en orden de
+  /// aparición.
+  pub fn find_nullables(&self) -> Vec<String> {
+    let mut nullables: Vec<String> = vec![];
+    let mut changed = true;
+
+    // Se repite hasta que ningún no terminal nuevo sea anulable: un no
+    // terminal es anulable si alguna de sus producciones solo contiene
+    // EPSILON o no terminales anulables.
+    while changed {
+      changed = false;
+
+      for (index, left) in self.sides.left.iter().enumerate() {
+        if nullables.contains(left) {
+          continue;
+        }
+
+        let production = self.side_to_prod(&self.sides.right[index]);
+        if production.iter().all(|el| el == "'" || nullables.contains(el)) {
+          nullables.push(String::from(left));
+          changed = true;
+        }
+      }
+    }
+
+    self.non_terminals
+      .iter()
+      .filter(|non_terminal| nullables.contains(non_terminal))
+      .cloned()
+      .collect()
+  }
+
+  /// Regresa, por cada no terminal productivo, la cadena de terminales más
+  /// corta que se puede derivar de él. Los no terminales que no derivan ninguna cadena de
+  /// terminales no aparecen en el resultado.
+  pub fn find_shortest_derivations(&self) -> HashMap<String, ShortestDerivation> {
+    let mut shortest: HashMap<String, ShortestDerivation> = HashMap::new();
+    let mut changed = true;
+
+    // Se repite mientras alguna derivación se vuelva más corta. Solo se
+    // reemplaza una derivación por otra estrictamente más corta, para que
+    // las producciones elegidas nunca formen ciclos.
+    while changed {
+      changed = false;
+
+      for (index, left) in self.sides.left.iter().enumerate() {
+        let mut sentence = vec![];
+        let mut productive = true;
+
+        for el in self.production_body(index) {
+          if self.non_terminals.contains(&el) {
+            match shortest.get(&el) {
+              Some(derivation) => sentence.extend(derivation.sentence.to_owned()),
+              None => {
+                productive = false;
+                break;
+              },
+            }
+          } else {
+            sentence.push(el);
+          }
+        }
+
+        if !productive {
+          continue;
+        }
+
+        let is_shorter = match shortest.get(left) {
+          Some(derivation) => sentence.len() < derivation.sentence.len(),
+          None => true,
+        };
+
+        if is_shorter {
+          shortest.insert(
+            String::from(left), ShortestDerivation { sentence },
+          );
+          changed = true;
+        }
+      }
+    }
+
+    shortest
+  }
+
+  /// Regresa los no terminales que aparecen en alguna forma sentencial
+  /// derivada del símbolo inicial, en orden de aparición.
+  pub fn find_reachable(&self) -> Vec<String> {
+    let mut reachable: Vec<String> = self.non_terminals.iter().take(1).cloned().collect();
+    let mut pending = reachable.to_owned();
+
+    while let Some(non_terminal) = pending.pop() {
+      for index in self.get_indexes_in_non_terminals(&non_terminal) {
+        for el in self.production_body(index) {
+          if self.non_terminals.contains(&el) && !reachable.contains(&el) {
+            reachable.push(el.to_owned());
+            pending.push(el);
+          }
+        }
+      }
+    }
+
+    self.non_terminals
+      .iter()
+      .filter(|non_terminal| reachable.contains(non_terminal))
+      .cloned()
+      .collect()
+  }
+
   /// Revisa si la gramática es LL(1) siguiendo las 3 condiciones.
   pub fn is_ll1(&mut self) -> bool {
-    for non_terminal in self.non_terminals.to_owned() {
+    for non_terminal in self.non_terminals.clone() {
       let indexes = &mut self.get_indexes_in_non_terminals(&non_terminal);
       // Si el no terminal solo tiene una produccieon o menos (no se si pueda)
       // suceder, no se aplica ninguna regla. Solo aplica para terminales de
@@ -210,7 +421,7 @@ impl Grammar {
         continue;
       }
 
-      for pair_of_indexes in indexes.to_owned().into_iter().combinations(2) {
+      for pair_of_indexes in indexes.iter().copied().combinations(2) {
         // Obtenemos objetos y estructuras necesarias para las tres reglas.
         let side_1 = self.sides.right[pair_of_indexes[0]].to_owned();
         let side_2 = self.sides.right[pair_of_indexes[1]].to_owned();
@@ -232,7 +443,7 @@ impl Grammar {
 
         // Aquí se validan tanto la primera como la segunda regla. Si los dos
         // derivan en epsilon, la intersección será diferente que cero.
-        if intersection.len() != 0 {
+        if !intersection.is_empty() {
           return false;
         }
 
@@ -245,7 +456,7 @@ impl Grammar {
             &follow_set,
           ).collect();
           
-          if third_rule_inter.len() != 0 {
+          if !third_rule_inter.is_empty() {
             return false;
           }
         }
@@ -258,91 +469,118 @@ impl Grammar {
             &follow_set,
           ).collect();
 
-          if third_rule_inter.len() != 0 {
+          if !third_rule_inter.is_empty() {
             return false;
           }
         }
       }
     }
 
-    return true;
+    true
   }
 
-  /// Regresa FOLLOW de un terminal.
+  /// Regresa FOLLOW de un no terminal. La primera vez se calculan los de
+  /// todos los no terminales (ver `find_all_follows`).
   pub fn find_follow(&mut self, non_terminal: &String) -> Vec<String> {
-    if self.follows.contains_key(non_terminal) {
-      match self.follows.get(non_terminal) {
-        Some(follow) => return follow.to_owned(),
-        None => {},
-      }
+    if !self.follows.contains_key(non_terminal) {
+      self.find_all_follows();
     }
 
-    let mut follow = vec![];
-    let mut _use_third_rule = false;
-    let indexes = self.get_indexes_in_non_terminals(non_terminal);
+    self.follows.get(non_terminal).cloned().unwrap_or_default()
+  }
+
+  /// Calcula FOLLOW de todos los no terminales. Las reglas se aplican hasta
+  /// que ningún conjunto cambia, porque la tercera regla puede hacer que dos
+  /// conjuntos dependan uno del otro, como en `A -> B APrime` y
+  /// `APrime -> ^ A`, donde FOLLOW(A) y FOLLOW(APrime) son iguales.
+  fn find_all_follows(&mut self) {
+    let mut follows: HashMap<String, Vec<String>> = self.non_terminals
+      .iter()
+      .map(|non_terminal| (non_terminal.to_owned(), vec![]))
+      .collect();
+    // Pares (A, B) tales que FOLLOW(A) está contenido en FOLLOW(B).
+    let mut inclusions: Vec<(String, String)> = vec![];
 
     // primera regla
-    if indexes.contains(&0) {
-      follow.push(String::from("$"));
+    if let Some(start) = self.sides.left.first() {
+      follows.get_mut(start).unwrap().push(String::from("$"));
     }
 
-    // segunda regla
-    let locations = self.find_in_right_side(non_terminal);
+    for index in 0..self.sides.left.len() {
+      let left = self.sides.left[index].to_owned();
+      let production = self.get_production(index);
 
-    for (right_index, prod_index) in locations {
-      _use_third_rule = false;
+      for (position, el) in production.iter().enumerate() {
+        if !self.non_terminals.contains(el) {
+          continue;
+        }
 
-      let production = self.get_production(right_index);
-      
-      // Si es el último elemento de la producción, aplicar la tercer regla.
-      if prod_index + 1 == production.len() {
-        _use_third_rule = true;
-      }
-      
-      // encontrar FIRST del lado derecho del no terminal.
-      // A -> aBb, entonces FOLLOW(B) = FIRST(b) excepto ' '
-      let right_side = &production[prod_index+1..production.len()];
-
-      // aplicar la segunda regla solamente si no es el último elemento
-      // de la producción.
-      if right_side.len() > 0 {
-        let right_first = self.find_first_production(&right_side.to_vec());
-        for element in right_first {
-          if element != "' '"  {
-            if !follow.contains(&element) {
+        // segunda regla: A -> aBb, entonces FOLLOW(B) contiene FIRST(b)
+        // excepto ' '.
+        let right_side = production[position + 1..].to_vec();
+        let mut use_third_rule = right_side.is_empty();
+
+        if !right_side.is_empty() {
+          for element in self.find_first_production(&right_side) {
+            let follow = follows.get_mut(el).unwrap();
+
+            if element == "' '" {
+              use_third_rule = true;
+            } else if !follow.contains(&element) {
               follow.push(element);
             }
-          } else {
-            _use_third_rule = true;
           }
         }
-      }
 
-      // con esta condición se rompe la recursividad en caso de que la
-      // producción a analizar sea la misma que la del no terminal.
-      if non_terminal == &self.sides.left[right_index] {
-        continue;
+        // tercera regla: FOLLOW(B) contiene FOLLOW(A).
+        if use_third_rule && *el != left {
+          inclusions.push((left.to_owned(), el.to_owned()));
+        }
       }
+    }
+
+    let mut changed = true;
+
+    while changed {
+      changed = false;
 
-      // se aplica la tercera regla si es necesario.
-      if _use_third_rule {
-        let non_terminal = self.sides.left[right_index].to_owned();
-        let next_follow = self.find_follow(&non_terminal);
-        for next in next_follow {
-          if !follow.contains(&next) {
-            follow.push(next);
+      for (from, into) in &inclusions {
+        for element in follows[from].clone() {
+          let follow = follows.get_mut(into).unwrap();
+
+          if !follow.contains(&element) {
+            follow.push(element);
+            changed = true;
           }
         }
       }
     }
 
-    self.follows.insert(String::from(non_terminal), follow.to_owned());
-    follow
+    for (non_terminal, mut follow) in follows {
+      self.sort_symbols(&mut follow);
+      self.follows.insert(non_terminal, follow);
+    }
+  }
+
+  /// Ordena un conjunto de símbolos según el orden de aparición de los
+  /// terminales en la gramática, para que los conjuntos FIRST y FOLLOW sean
+  /// siempre iguales entre ejecuciones. `$` y EPSILON van al final.
+  fn sort_symbols(&self, symbols: &mut [String]) {
+    let terminals = &self.terminals;
+
+    symbols.sort_by_key(|symbol| match symbol.as_str() {
+      "$" => terminals.len(),
+      "' '" => terminals.len() + 1,
+      _ => terminals
+        .iter()
+        .position(|terminal| terminal == symbol)
+        .unwrap_or(terminals.len() + 2),
+    });
   }
 
   /// Regresa los índices de las apariciones del no terminal en el lado
   /// izquierdo de la gramática.
-  fn get_indexes_in_non_terminals(&self, non_terminal: &String) -> Vec<usize> {
+  pub fn get_indexes_in_non_terminals(&self, non_terminal: &String) -> Vec<usize> {
       let mut indexes = vec![];
 
       for (index, value) in self.sides.left.iter().enumerate() {
@@ -353,21 +591,6 @@ impl Grammar {
       indexes
   }
 
-  /// Busca un no terminal del lado derecho de la gramática.
-  fn find_in_right_side(&self, non_terminal: &String) -> Vec<(usize, usize)> {
-    let mut result = vec![];
-
-    for (right_index, prod) in self.sides.right.iter().enumerate() {
-      for (prod_index, el) in self.side_to_prod(prod).iter().enumerate() {
-        if non_terminal == el {
-          result.push((right_index, prod_index));
-        }
-      }
-    }
-
-    result
-  }
-
   /// Genera todas las producciones en la gramática y las guarda en un caché.
   pub fn find_all_productions(&mut self) {
     for (index, _) in self.sides.left.iter().enumerate() {
@@ -378,12 +601,53 @@ impl Grammar {
     }
   }
 
+  /// Regresa los símbolos del lado derecho de la producción con el índice
+  /// dado, sin los elementos que representan EPSILON.
+  pub fn production_body(&self, index: usize) -> Vec<String> {
+    self.sides.right[index]
+      .split(' ')
+      .filter(|el| *el != "'")
+      .map(String::from)
+      .collect()
+  }
+
+  /// Regresa la producción con el índice dado en la forma `A -> ( A )`.
+  /// EPSILON se muestra como `ε`.
+  pub fn production_as_string(&self, index: usize) -> String {
+    let body = self.production_body(index);
+
+    format!(
+      "{} -> {}",
+      self.sides.left[index],
+      if body.is_empty() {String::from("ε")} else {body.join(" ")},
+    )
+  }
+
+  /// Regresa las producciones como líneas de un archivo de entrada, en la
+  /// forma `A -> ( A )`.
+  pub fn production_lines(&self) -> Vec<String> {
+    self.sides.left
+      .iter()
+      .zip(self.sides.right.iter())
+      .map(|(left, right)| format!("{} -> {}", left, right))
+      .collect()
+  }
+
+  /// Regresa FIRST de un no terminal ya calculado, con EPSILON como `ε`.
+  pub fn first_as_symbols(&self, non_terminal: &str) -> Vec<String> {
+    self.firsts.get(non_terminal)
+      .cloned()
+      .unwrap_or_default()
+      .into_iter()
+      .map(|el| if el == "' '" {String::from("ε")} else {el})
+      .collect()
+  }
+
   /// Construye una sola producción de acuerdo a un índice del lado derecho.
   fn get_production(&mut self, index: usize) -> Vec<String> {
     if self.productions.contains_key(&index) {
-      match self.productions.get(&index) {
-        Some(production) => return production.to_owned(),
-        None => {},
+      if let Some(production) = self.productions.get(&index) {
+        return production.to_owned();
       }
     }
     let production = self.side_to_prod(&self.sides.right[index]);
@@ -395,7 +659,7 @@ impl Grammar {
   }
 
   /// Convierte un string a un vector de elementos (una producción).
-  fn side_to_prod(&self, side: &String) -> Vec<String> {
+  fn side_to_prod(&self, side: &str) -> Vec<String> {
     let elements_in_production = side.split(' ').collect::<Vec<_>>();
     let mut production = vec![];
 
@@ -406,3 +670,128 @@ impl Grammar {
     production
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use crate::examples;
+  use super::Grammar;
+
+  fn follow(grammar: &mut Grammar, non_terminal: &str) -> Vec<String> {
+    grammar.find_follow(&String::from(non_terminal))
+  }
+
+  fn assert_follows(mut grammar: Grammar, expected: &[(&str, &[&str])]) {
+    for (non_terminal, symbols) in expected {
+      assert_eq!(follow(&mut grammar, non_terminal), *symbols, "FOLLOW({})", non_terminal);
+    }
+  }
+
+  #[test]
+  fn alternatives_and_continuation_lines() {
+    let grammar = examples::unanalyzed(&["S -> a S | ε", "  | b", "A -> | c"]);
+
+    assert_eq!(grammar.sides.left, vec!["S", "S", "S", "A", "A"]);
+    assert_eq!(grammar.sides.right, vec!["a S", "' '", "b", "' '", "c"]);
+    assert_eq!(grammar.sides.lines, vec![0, 0, 1, 2, 2]);
+  }
+
+  #[test]
+  fn invalid_production_lines() {
+    let lines = |text: &[&str]| text.iter().map(|line| line.to_string()).collect();
+
+    assert_eq!(
+      Grammar::try_new(lines(&["  | a", "S -> b"])).err().unwrap(),
+      "`| a`: `|` sin una producción anterior",
+    );
+    assert_eq!(
+      Grammar::try_new(lines(&["S -> a", "S a b"])).err().unwrap(),
+      "se esperaba una producción `A -> α`, se encontró `S a b`",
+    );
+    assert_eq!(
+      Grammar::try_new(lines(&[" -> a"])).err().unwrap(),
+      "se esperaba una producción `A -> α`, se encontró `-> a`",
+    );
+  }
+
+  #[test]
+  fn firsts_in_grammar_order() {
+    let mut grammar = examples::grammar_from(&["S -> A | c", "A -> b | a | ' '"]);
+
+    assert_eq!(grammar.find_single_first(&String::from("A")), vec!["b", "a", "' '"]);
+    assert_eq!(grammar.find_single_first(&String::from("S")), vec!["c", "b", "a", "' '"]);
+  }
+
+  #[test]
+  fn follows_of_examples() {
+    assert_follows(examples::grammar(1), &[
+      ("E", &[")", "$"]),
+      ("EPrime", &[")", "$"]),
+      ("T", &["+", ")", "$"]),
+      ("TPrime", &["+", ")", "$"]),
+      ("F", &["+", "*", ")", "$"]),
+    ]);
+    assert_follows(examples::grammar(2), &[
+      ("goal", &["$"]),
+      ("A", &[")", "$"]),
+      ("two", &[")", "$"]),
+    ]);
+    assert_follows(examples::grammar(3), &[
+      ("E", &["+", ")", "$"]),
+      ("T", &["+", "*", ")", "$"]),
+      ("F", &["+", "*", ")", "$"]),
+    ]);
+    assert_follows(examples::grammar(4), &[
+      ("A", &["$"]),
+      ("B", &["$"]),
+      ("C", &["$"]),
+      ("D", &["$"]),
+    ]);
+    assert_follows(examples::grammar(5), &[
+      ("bexpr", &["or", ")", "$"]),
+      ("bterm", &["or", "and", ")", "$"]),
+      ("bfactor", &["or", "and", ")", "$"]),
+    ]);
+    assert_follows(examples::grammar(6), &[
+      ("S", &["$"]),
+      ("A", &["a"]),
+      ("APrime", &["a"]),
+    ]);
+  }
+
+  #[test]
+  fn follow_through_nullable_tail() {
+    // En `A -> B D`, D deriva EPSILON, así que FOLLOW(B) contiene FIRST(D) y
+    // FOLLOW(A).
+    assert_follows(examples::grammar(7), &[
+      ("S", &["$"]),
+      ("A", &["a"]),
+      ("B", &["a", "d"]),
+      ("D", &["a"]),
+    ]);
+  }
+
+  #[test]
+  fn follow_with_mutual_inclusion() {
+    // FOLLOW(E) contiene FOLLOW(EPrime) y al revés.
+    let grammar = examples::grammar_from(&[
+      "S -> ( E ) | E",
+      "E -> T EPrime",
+      "EPrime -> ^ E | ' '",
+      "T -> id",
+    ]);
+
+    assert_follows(grammar, &[
+      ("S", &["$"]),
+      ("E", &[")", "$"]),
+      ("EPrime", &[")", "$"]),
+      ("T", &[")", "^", "$"]),
+    ]);
+  }
+
+  #[test]
+  fn follow_of_start_symbol_that_appears_on_the_right() {
+    let grammar = examples::grammar_from(&["S -> a S b | ' '"]);
+
+    assert_follows(grammar, &[("S", &["b", "$"])]);
+  }
+}
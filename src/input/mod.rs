@@ -0,0 +1,185 @@
+use super::grammar::{Grammar};
+
+/// Separador entre la cadena a evaluar y su resultado esperado.
+const ANNOTATION_SEPARATOR: &str = " # ";
+/// Línea del archivo de entrada en la que está la primera producción (la
+/// primera línea tiene la cantidad de producciones y de entradas).
+pub const FIRST_PRODUCTION_LINE: usize = 2;
+
+/// Estructura que representa una cadena de entrada a evaluar.
+///
+/// Una entrada puede llevar al final el resultado esperado de su evaluación,
+/// separado por ` # `:
+///
+/// ```txt
+/// ( ( a ) ) # accept
+/// ( a ) ) # reject
+/// ```
+///
+/// Una cadena rechazada puede indicar también la posición (empezando en 1)
+/// del elemento donde el parser debe encontrar el error:
+///
+/// ```txt
+/// ( a ) ) # reject at 4
+/// ```
+pub struct Input {
+  /// Cadena a evaluar, sin la anotación.
+  pub text: String,
+  /// Resultado esperado: `Some(true)` si la cadena debe ser aceptada,
+  /// `Some(false)` si debe ser rechazada y `None` si no tiene anotación.
+  pub expected: Option<bool>,
+  /// Posición esperada del error si la cadena debe ser rechazada.
+  pub error_position: Option<usize>,
+}
+
+impl Input {
+  /// Construye una entrada a partir de una línea leída del archivo.
+  ///
+  /// Si la línea no termina con una anotación válida (`accept`, `reject` o
+  /// `reject at N`), toda la línea se toma como la cadena a evaluar.
+  pub fn parse(line: &str) -> Self {
+    if let Some(position) = line.rfind(ANNOTATION_SEPARATOR) {
+      let annotation = line[position + ANNOTATION_SEPARATOR.len()..]
+        .trim()
+        .to_lowercase();
+      let words: Vec<&str> = annotation.split_whitespace().collect();
+      let annotation = match words.as_slice() {
+        ["accept"] => Some((true, None)),
+        ["reject"] => Some((false, None)),
+        ["reject", "at", error_position] => match error_position.parse() {
+          Ok(error_position) => Some((false, Some(error_position))),
+          Err(_) => None,
+        },
+        _ => None,
+      };
+
+      if let Some((expected, error_position)) = annotation {
+        return Input {
+          text: String::from(line[..position].trim_end()),
+          expected: Some(expected),
+          error_position,
+        };
+      }
+    }
+
+    Input {
+      text: String::from(line),
+      expected: None,
+      error_position: None,
+    }
+  }
+
+  /// Crea una entrada que debe ser aceptada a partir de sus elementos.
+  pub fn accepted(sentence: &[String]) -> Self {
+    Input {
+      text: sentence.join(" "),
+      expected: Some(true),
+      error_position: None,
+    }
+  }
+
+  /// Regresa la entrada como una línea del archivo, con su anotación si
+  /// tiene una.
+  pub fn to_line(&self) -> String {
+    match (self.expected, self.error_position) {
+      (Some(false), Some(error_position)) => format!(
+        "{}{}reject at {}", self.text, ANNOTATION_SEPARATOR, error_position,
+      ),
+      (Some(expected), _) => format!(
+        "{}{}{}", self.text, ANNOTATION_SEPARATOR, result_as_annotation(expected),
+      ),
+      (None, _) => self.text.to_owned(),
+    }
+  }
+}
+
+/// Construye el contenido de un archivo de entrada: la cantidad de
+/// producciones y entradas, las producciones y las entradas.
+pub fn format_input_file(productions: &[String], inputs: &[Input]) -> String {
+  let mut lines = vec![format!("{} {}", productions.len(), inputs.len())];
+  lines.extend(productions.iter().cloned());
+  lines.extend(inputs.iter().map(|input| input.to_line()));
+
+  lines.join("\n")
+}
+
+/// Regresa la palabra usada en las anotaciones para un resultado.
+pub fn result_as_annotation(result: bool) -> &'static str {
+  if result {"accept"} else {"reject"}
+}
+
+/// Regresa la línea del archivo de entrada en la que está la producción con
+/// el índice dado.
+pub fn production_line(grammar: &Grammar, index: usize) -> usize {
+  grammar.sides.lines[index] + FIRST_PRODUCTION_LINE
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::analyzer::LL1Analyzer;
+  use crate::examples;
+  use super::*;
+
+  #[test]
+  fn parse_annotations() {
+    let input = Input::parse("( a ) # accept");
+    assert_eq!((input.text.as_str(), input.expected, input.error_position), ("( a )", Some(true), None));
+
+    let input = Input::parse("( a ) ) # REJECT");
+    assert_eq!((input.text.as_str(), input.expected, input.error_position), ("( a ) )", Some(false), None));
+
+    let input = Input::parse("( a ) ) # reject at 4");
+    assert_eq!((input.text.as_str(), input.expected, input.error_position), ("( a ) )", Some(false), Some(4)));
+  }
+
+  #[test]
+  fn line_without_valid_annotation_is_the_input() {
+    for line in &["( a )", "a # b", "a # reject at x", "a #accept"] {
+      let input = Input::parse(line);
+      assert_eq!((input.text.as_str(), input.expected, input.error_position), (*line, None, None));
+    }
+  }
+
+  #[test]
+  fn to_line_keeps_the_annotation() {
+    for line in &["( a ) # accept", "( a ) ) # reject", "( a ) ) # reject at 4", "( a )"] {
+      assert_eq!(Input::parse(line).to_line(), *line);
+    }
+  }
+
+  #[test]
+  fn format_input_file_with_counts() {
+    let productions = vec![String::from("S -> a")];
+    let inputs = vec![Input::accepted(&[String::from("a")]), Input::parse("a a # reject at 2")];
+
+    assert_eq!(format_input_file(&productions, &inputs), "1 2\nS -> a\na # accept\na a # reject at 2");
+  }
+
+  #[test]
+  fn examples_match_their_annotations() {
+    for number in &[1, 2, 7] {
+      let (_, inputs) = examples::read(*number);
+      let mut grammar = examples::grammar(*number);
+      let mut analyzer = LL1Analyzer::new(&mut grammar);
+      analyzer.build_table();
+
+      for input in inputs {
+        assert_eq!(Some(analyzer.eval(&input.text)), input.expected, "input{}: {}", number, input.text);
+        if input.error_position.is_some() {
+          assert_eq!(analyzer.trace(&input.text).error_position, input.error_position);
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn error_position_of_rejected_input() {
+    let mut grammar = examples::grammar(1);
+    let mut analyzer = LL1Analyzer::new(&mut grammar);
+    analyzer.build_table();
+
+    assert_eq!(analyzer.trace("id * * id").error_position, Some(3));
+    assert_eq!(analyzer.trace("id + ( id").error_position, Some(5));
+    assert_eq!(analyzer.trace("id + id").error_position, None);
+  }
+}
@@ -0,0 +1,20 @@
+//! Análisis de gramáticas libres de contexto: conjuntos FIRST y FOLLOW,
+//! tabla de parseo LL(1), transformaciones de la gramática y generación de
+//! parsers.
+//!
+//! El ejecutable `lexian` usa esta biblioteca. El módulo `build` permite
+//! generar un parser desde el `build.rs` de otro proyecto, y `translation`
+//! ejecutar acciones semánticas al analizar una cadena.
+
+pub mod analyzer;
+pub mod build;
+pub mod codegen;
+pub mod generator;
+pub mod grammar;
+pub mod input;
+pub mod linter;
+pub mod transform;
+pub mod translation;
+
+#[cfg(test)]
+mod examples;
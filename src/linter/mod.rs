@@ -0,0 +1,313 @@
+use std::collections::{VecDeque};
+use super::grammar::{Grammar};
+use super::input::{production_line};
+
+/// Tipo de problema encontrado en la gramática.
+#[derive(Clone, Copy)]
+pub enum LintKind {
+  /// No terminal que no aparece en ninguna derivación del símbolo inicial.
+  Unreachable,
+  /// No terminal que no deriva ninguna cadena de terminales.
+  Unproductive,
+  /// Terminal cuyo nombre parece el de un no terminal.
+  SuspiciousTerminal,
+  /// Producción escrita más de una vez.
+  DuplicateProduction,
+  /// No terminal que se deriva a sí mismo: A =>+ A.
+  Cycle,
+}
+
+impl LintKind {
+  /// Nombre corto del tipo de problema, para mostrarlo junto al mensaje.
+  pub fn name(&self) -> &'static str {
+    match self {
+      LintKind::Unreachable => "unreachable",
+      LintKind::Unproductive => "unproductive",
+      LintKind::SuspiciousTerminal => "suspicious-terminal",
+      LintKind::DuplicateProduction => "duplicate-production",
+      LintKind::Cycle => "cycle",
+    }
+  }
+}
+
+/// Problema encontrado en la gramática.
+pub struct Lint {
+  /// Línea del archivo de entrada donde está la producción del problema.
+  pub line: usize,
+  pub kind: LintKind,
+  pub message: String,
+}
+
+/// Revisa la gramática y regresa los problemas encontrados, ordenados por
+/// línea.
+///
+/// Estos problemas no impiden construir la tabla de parseo, pero suelen ser
+/// errores al escribir la gramática. Por ejemplo, `find_terminals` toma como
+/// terminal cualquier símbolo que no está del lado izquierdo, así que un no
+/// terminal mal escrito se vuelve un terminal sin ningún aviso.
+pub fn lint_grammar(grammar: &Grammar) -> Vec<Lint> {
+  let mut lints = vec![];
+
+  find_unreachable(grammar, &mut lints);
+  find_unproductive(grammar, &mut lints);
+  find_suspicious_terminals(grammar, &mut lints);
+  find_duplicates(grammar, &mut lints);
+  find_cycles(grammar, &mut lints);
+
+  lints.sort_by_key(|lint| lint.line);
+  lints
+}
+
+/// Regresa la línea de la primera producción de un no terminal.
+fn first_line_of(grammar: &Grammar, non_terminal: &String) -> usize {
+  production_line(grammar, grammar.get_indexes_in_non_terminals(non_terminal)[0])
+}
+
+fn find_unreachable(grammar: &Grammar, lints: &mut Vec<Lint>) {
+  let start = match grammar.non_terminals.first() {
+    Some(start) => start,
+    None => return,
+  };
+  let reachable = grammar.find_reachable();
+
+  for non_terminal in &grammar.non_terminals {
+    if !reachable.contains(non_terminal) {
+      lints.push(Lint {
+        line: first_line_of(grammar, non_terminal),
+        kind: LintKind::Unreachable,
+        message: format!(
+          "`{}` no se puede alcanzar desde el símbolo inicial `{}`",
+          non_terminal, start,
+        ),
+      });
+    }
+  }
+}
+
+fn find_unproductive(grammar: &Grammar, lints: &mut Vec<Lint>) {
+  let shortest = grammar.find_shortest_derivations();
+
+  for non_terminal in &grammar.non_terminals {
+    if !shortest.contains_key(non_terminal) {
+      lints.push(Lint {
+        line: first_line_of(grammar, non_terminal),
+        kind: LintKind::Unproductive,
+        message: format!(
+          "`{}` no deriva ninguna cadena de terminales", non_terminal,
+        ),
+      });
+    }
+  }
+}
+
+/// Busca terminales que difieren en un solo caracter de un no terminal o
+/// que, cuando todos los no terminales empiezan con mayúscula, también
+/// empiezan con mayúscula. Los no terminales de menos de tres caracteres no
+/// se comparan, porque es común usar `A` y `a` en la misma gramática.
+fn find_suspicious_terminals(grammar: &Grammar, lints: &mut Vec<Lint>) {
+  let capitalized = |symbol: &String| {
+    symbol.chars().next().is_some_and(|first| first.is_uppercase())
+  };
+  let all_capitalized = grammar.non_terminals.iter().all(capitalized);
+
+  for terminal in &grammar.terminals {
+    let similar = grammar.non_terminals.iter().find(|non_terminal| {
+      non_terminal.chars().count() >= 3 && edit_distance(terminal, non_terminal) == 1
+    });
+
+    let reason = match similar {
+      Some(non_terminal) => format!("se parece al no terminal `{}`", non_terminal),
+      None if all_capitalized && capitalized(terminal) => String::from(
+        "empieza con mayúscula como los no terminales, pero no tiene producciones",
+      ),
+      None => continue,
+    };
+
+    let index = (0..grammar.sides.left.len())
+      .find(|index| grammar.production_body(*index).contains(terminal))
+      .unwrap();
+
+    lints.push(Lint {
+      line: production_line(grammar, index),
+      kind: LintKind::SuspiciousTerminal,
+      message: format!("el terminal `{}` {}", terminal, reason),
+    });
+  }
+}
+
+fn find_duplicates(grammar: &Grammar, lints: &mut Vec<Lint>) {
+  for index in 0..grammar.sides.left.len() {
+    let original = (0..index).find(|other| {
+      grammar.sides.left[*other] == grammar.sides.left[index]
+        && grammar.production_body(*other) == grammar.production_body(index)
+    });
+
+    if let Some(original) = original {
+      lints.push(Lint {
+        line: production_line(grammar, index),
+        kind: LintKind::DuplicateProduction,
+        message: format!(
+          "`{}` repite la producción de la línea {}",
+          grammar.production_as_string(index), production_line(grammar, original),
+        ),
+      });
+    }
+  }
+}
+
+/// Busca no terminales que se derivan a sí mismos usando producciones cuyo
+/// resto deriva en EPSILON. Cada ciclo se reporta una sola vez.
+fn find_cycles(grammar: &Grammar, lints: &mut Vec<Lint>) {
+  let nullables = grammar.find_nullables();
+  // Por cada producción, los no terminales que puede derivar solos: los
+  // demás símbolos de la producción derivan en EPSILON.
+  let units: Vec<Vec<String>> = (0..grammar.sides.left.len())
+    .map(|index| {
+      let body = grammar.production_body(index);
+      body
+        .iter()
+        .enumerate()
+        .filter(|(position, symbol)| {
+          grammar.non_terminals.contains(symbol)
+            && body
+              .iter()
+              .enumerate()
+              .all(|(other, el)| other == *position || nullables.contains(el))
+        })
+        .map(|(_, symbol)| symbol.to_owned())
+        .collect()
+    })
+    .collect();
+
+  let mut reported: Vec<String> = vec![];
+  for non_terminal in &grammar.non_terminals {
+    if reported.contains(non_terminal) {
+      continue;
+    }
+
+    if let Some(path) = find_unit_path(grammar, &units, non_terminal) {
+      let (symbols, indexes): (Vec<String>, Vec<usize>) = path.into_iter().unzip();
+
+      lints.push(Lint {
+        line: production_line(grammar, indexes[0]),
+        kind: LintKind::Cycle,
+        message: format!(
+          "ciclo {} => {}", non_terminal, symbols.join(" => "),
+        ),
+      });
+      reported.extend(symbols);
+    }
+  }
+}
+
+/// Busca a lo ancho el camino más corto de derivaciones de un solo no
+/// terminal que regresa a `non_terminal`. Regresa cada no terminal del
+/// camino con la producción que lo deriva.
+fn find_unit_path(
+  grammar: &Grammar, units: &[Vec<String>], non_terminal: &String,
+) -> Option<Vec<(String, usize)>> {
+  let mut queue = VecDeque::new();
+  let mut visited: Vec<String> = vec![];
+  queue.push_back((non_terminal.to_owned(), vec![]));
+
+  while let Some((current, path)) = queue.pop_front() {
+    for index in grammar.get_indexes_in_non_terminals(&current) {
+      for next in &units[index] {
+        let mut next_path: Vec<(String, usize)> = path.to_owned();
+        next_path.push((next.to_owned(), index));
+
+        if next == non_terminal {
+          return Some(next_path);
+        }
+        if !visited.contains(next) {
+          visited.push(next.to_owned());
+          queue.push_back((next.to_owned(), next_path));
+        }
+      }
+    }
+  }
+
+  None
+}
+
+/// Regresa la cantidad mínima de caracteres que hay que insertar, eliminar o
+/// reemplazar para convertir un texto en otro.
+fn edit_distance(a: &str, b: &str) -> usize {
+  let b: Vec<char> = b.chars().collect();
+  let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+  for (i, a_char) in a.chars().enumerate() {
+    let mut current = vec![i + 1];
+
+    for (j, b_char) in b.iter().enumerate() {
+      let replace = previous[j] + if a_char == *b_char {0} else {1};
+      current.push(replace.min(previous[j + 1] + 1).min(current[j] + 1));
+    }
+
+    previous = current;
+  }
+
+  previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::examples;
+  use super::{edit_distance, lint_grammar};
+
+  fn lints(productions: &[&str]) -> Vec<(usize, &'static str, String)> {
+    lint_grammar(&examples::unanalyzed(productions))
+      .into_iter()
+      .map(|lint| (lint.line, lint.kind.name(), lint.message))
+      .collect()
+  }
+
+  #[test]
+  fn examples_have_no_problems() {
+    for number in 1..=7 {
+      assert!(lint_grammar(&examples::grammar(number)).is_empty(), "input{}", number);
+    }
+  }
+
+  #[test]
+  fn unreachable_and_unproductive() {
+    assert_eq!(lints(&["S -> a | B", "B -> b B", "C -> c"]), vec![
+      (3, "unproductive", String::from("`B` no deriva ninguna cadena de terminales")),
+      (4, "unreachable", String::from("`C` no se puede alcanzar desde el símbolo inicial `S`")),
+    ]);
+  }
+
+  #[test]
+  fn suspicious_terminals() {
+    assert_eq!(lints(&["Expr -> Term", "Term -> Tem | id"]), vec![
+      (3, "suspicious-terminal", String::from("el terminal `Tem` se parece al no terminal `Term`")),
+    ]);
+    assert_eq!(lints(&["S -> A", "A -> B | a"]), vec![
+      (3, "suspicious-terminal", String::from(
+        "el terminal `B` empieza con mayúscula como los no terminales, pero no tiene producciones",
+      )),
+    ]);
+  }
+
+  #[test]
+  fn duplicate_production() {
+    assert_eq!(lints(&["S -> a", "S -> b", "S -> a"]), vec![
+      (4, "duplicate-production", String::from("`S -> a` repite la producción de la línea 2")),
+    ]);
+  }
+
+  #[test]
+  fn cycle_through_nullable_symbols() {
+    assert_eq!(lints(&["S -> A", "A -> B C | a", "B -> S", "C -> c | ' '"]), vec![
+      (2, "cycle", String::from("ciclo S => A => B => S")),
+    ]);
+  }
+
+  #[test]
+  fn distance_between_names() {
+    assert_eq!(edit_distance("Term", "Tem"), 1);
+    assert_eq!(edit_distance("expr", "expr"), 0);
+    assert_eq!(edit_distance("", "abc"), 3);
+    assert_eq!(edit_distance("kitten", "sitting"), 3);
+  }
+}
@@ -1,12 +1,71 @@
-mod grammar;
-mod analyzer;
+mod cli;
 
-use std::fs::OpenOptions;
-use std::io::{self, BufRead};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader};
 use std::io::prelude::*;
+use std::path::Path;
+use std::process;
 use std::time::{SystemTime, UNIX_EPOCH, Duration};
-use grammar::{Grammar};
-use analyzer::{LL1Analyzer};
+use lexian::grammar::{Grammar};
+use lexian::analyzer::{LL1Analyzer};
+use cli::{Arguments};
+use lexian::codegen::{
+  generate_c_parser, generate_rust_ast_parser, generate_rust_descent_parser, generate_rust_parser,
+  generate_typescript_parser,
+};
+use lexian::generator::{
+  Generator, MutationKind, Random, compare_languages, enumerate_language, find_coverage,
+  find_mutants,
+};
+use lexian::input::{Input, format_input_file, production_line, result_as_annotation};
+use lexian::linter::{lint_grammar};
+use lexian::transform::{
+  expand_precedence, grammar_with_precedence, remove_epsilon_productions, remove_left_recursion,
+  remove_unit_productions, remove_useless_symbols, to_greibach,
+};
+
+/// Modos de ejecución del programa, elegidos con el primer argumento.
+#[derive(PartialEq)]
+enum Mode {
+  /// Construye el archivo HTML con la tabla y los resultados (por defecto).
+  Report,
+  /// Compara los resultados con los esperados en cada entrada.
+  Test,
+  /// Imprime el análisis de la gramática en formato JSON.
+  Json,
+  /// Imprime la gramática, sus conjuntos y la tabla en Markdown.
+  Markdown,
+  /// Imprime la gramática, sus conjuntos y la tabla en LaTeX.
+  Latex,
+  /// Genera cadenas al azar de la gramática.
+  Generate,
+  /// Genera cadenas que cubren todas las casillas de la tabla de parseo.
+  Coverage,
+  /// Genera cadenas válidas e inválidas a partir de cambios a las válidas.
+  Mutate,
+  /// Enumera todas las cadenas de la gramática hasta cierta longitud.
+  Enumerate,
+  /// Compara los lenguajes de dos gramáticas leídas de archivos.
+  Compare,
+  /// Busca problemas comunes al escribir la gramática.
+  Lint,
+  /// Imprime la gramática sin símbolos inútiles.
+  Reduce,
+  /// Imprime la gramática sin producciones EPSILON.
+  RemoveEpsilon,
+  /// Imprime la gramática sin producciones unitarias.
+  RemoveUnit,
+  /// Imprime la gramática sin recursión por la izquierda.
+  RemoveLeftRecursion,
+  /// Imprime la gramática en la forma normal de Greibach.
+  Greibach,
+  /// Imprime la gramática con las declaraciones de precedencia expandidas.
+  ExpandPrecedence,
+  /// Imprime el archivo de entrada con la gramática en forma canónica.
+  Fmt,
+  /// Genera el código de un parser para la gramática.
+  Codegen,
+}
 
 /// La función principal que será llamada al ejecutar el programa.
 /// 
@@ -14,72 +73,770 @@ use analyzer::{LL1Analyzer};
 /// 
 /// 1. Se leen las producciones línea por línea de un archivo.
 /// 2. Se se extraen los diferentes lados de las producciones: derecho e
-/// izquierdo.
+///    izquierdo.
 /// 3. Del lado izquierdo se obtienen los elementos no terminales.
 /// 4. Del lado derecho se eliminan elementos no terminales y se obtienen
-/// terminales.
+///    terminales.
 /// 5. Por cada no terminal, se obtienen los conjuntos FIRST y FOLLOW de cada
-/// non terminal y se imprimen en la consola.
+///    non terminal.
 /// 6. Si la gramática es LL(1), continúa con la evaluación de cadenas de 
-/// texto, si no, se detiene la ejecución y se notifica al usuario.
+///    texto, si no, se detiene la ejecución y se notifica al usuario.
 /// 7. Se construye la tabla de parseo.
 /// 8. Las entradas son evaluadas una por una.
-/// 9. Se construye el archivo de salida.
+/// 9. Se construye el archivo de salida: un reporte HTML con la gramática,
+///    los conjuntos FIRST y FOLLOW, la tabla de parseo y los pasos de cada
+///    entrada.
+///
+/// Si el programa se ejecuta como `lexian test`, en lugar de construir el
+/// archivo de salida se comparan los resultados con los esperados en cada
+/// entrada y se termina con error si alguno no coincide.
+///
+/// Si el programa se ejecuta como `lexian json`, `lexian markdown` o
+/// `lexian latex`, se imprime en la consola el análisis de la gramática en
+/// ese formato, aunque no sea LL(1).
+///
+/// Si el programa se ejecuta como `lexian generate`, se imprime un archivo de
+/// entrada con la misma gramática y cadenas generadas al azar a partir de
+/// ella (ver `run_generator`). Con `lexian coverage` las cadenas son las
+/// mínimas para usar todas las casillas de la tabla de parseo, y con
+/// `lexian mutate` se agregan cadenas inválidas (ver `run_mutations`).
+///
+/// Si el programa se ejecuta como `lexian enumerate`, se imprime un archivo
+/// de entrada con todas las cadenas de la gramática hasta cierta longitud
+/// (ver `run_enumeration`). Con `lexian compare a.txt b.txt` se comparan
+/// los lenguajes de las gramáticas de ambos archivos (ver `run_comparison`).
+///
+/// Si el programa se ejecuta como `lexian lint`, se imprimen los problemas
+/// encontrados en la gramática con su número de línea (ver `run_linter`).
+/// Con `lexian reduce` se imprime el archivo de entrada sin los símbolos
+/// inútiles de la gramática, y con la opción `--reduce` cualquier otro modo
+/// analiza la gramática reducida (ver `reduce_grammar`). De la misma forma,
+/// `lexian remove-epsilon`, `lexian remove-unit` y
+/// `lexian remove-left-recursion` imprimen la gramática sin producciones
+/// EPSILON, sin producciones unitarias o sin recursión por la izquierda, y
+/// `lexian greibach` la imprime en la forma normal de Greibach (ver
+/// `verify_transform`).
+///
+/// Las declaraciones de precedencia (`%left`, `%right` y `%nonassoc`) se
+/// expanden en una gramática estratificada antes de cualquier modo, excepto
+/// `fmt` (ver `expand_precedence`). `lint` y `reduce` revisan la gramática
+/// antes de expandirla, para señalar las líneas del archivo. `lexian
+/// expand-precedence` imprime la gramática expandida.
+///
+/// Si el programa se ejecuta como `lexian fmt`, se imprime el archivo de
+/// entrada con las producciones agrupadas por no terminal y alineadas (ver
+/// `Grammar::formatted_lines`). Con `--sort` también se ordenan.
+///
+/// Si el programa se ejecuta como `lexian codegen`, se genera el código de un
+/// parser para la gramática que no depende de lexian (ver `run_codegen`).
 fn main() -> io::Result<()> {
+  let arguments = Arguments::from_env();
+  let mode = match arguments.mode.as_deref() {
+    None => Mode::Report,
+    Some("test") => Mode::Test,
+    Some("json") => Mode::Json,
+    Some("markdown") => Mode::Markdown,
+    Some("latex") => Mode::Latex,
+    Some("generate") => Mode::Generate,
+    Some("coverage") => Mode::Coverage,
+    Some("mutate") => Mode::Mutate,
+    Some("enumerate") => Mode::Enumerate,
+    Some("compare") => Mode::Compare,
+    Some("lint") => Mode::Lint,
+    Some("reduce") => Mode::Reduce,
+    Some("remove-epsilon") => Mode::RemoveEpsilon,
+    Some("remove-unit") => Mode::RemoveUnit,
+    Some("remove-left-recursion") => Mode::RemoveLeftRecursion,
+    Some("greibach") => Mode::Greibach,
+    Some("expand-precedence") => Mode::ExpandPrecedence,
+    Some("fmt") => Mode::Fmt,
+    Some("codegen") => Mode::Codegen,
+    Some(other) => {
+      eprintln!(
+        "Modo desconocido: {}. Modos disponibles: test, json, markdown, latex, \
+        generate, coverage, mutate, enumerate, compare, lint, reduce, remove-epsilon, remove-unit, \
+        remove-left-recursion, greibach, expand-precedence, fmt, codegen",
+        other,
+      );
+      process::exit(2);
+    },
+  };
+
+  if mode == Mode::Compare {
+    match run_comparison(&arguments) {
+      Ok(true) => return Ok(()),
+      Ok(false) => process::exit(1),
+      Err(e) => {
+        eprintln!("{}", e);
+        process::exit(2);
+      },
+    }
+  }
+
   let (productions, inputs) = read_productions();
 
-  let mut grammar = Grammar::new(productions);
-  grammar.find_non_terminals();
-  grammar.find_terminals();
-  grammar.find_all_productions();
+  // Las anotaciones no son parte de la gramática, así que se leen antes.
+  if mode == Mode::Codegen && arguments.value("target") == Some("rust-ast") {
+    if let Err(e) = generate_rust_ast_parser(&productions).and_then(|code| output_code(&code, &arguments)) {
+      eprintln!("{}", e);
+      process::exit(1);
+    }
+    return Ok(())
+  }
+
+  let (precedences, mut grammar) = match grammar_with_precedence(&productions) {
+    Ok(split) => split,
+    Err(e) => {
+      eprintln!("{}", e);
+      process::exit(2);
+    },
+  };
 
-  for non_terminal in grammar.non_terminals.to_owned() {
-    grammar.find_single_first(&non_terminal);
-    grammar.find_follow(&non_terminal);
+  if mode == Mode::Fmt {
+    let lines: Vec<String> = precedences
+      .iter()
+      .map(|precedence| precedence.declaration())
+      .chain(grammar.formatted_lines(arguments.has("sort")))
+      .collect();
+    println!("{}", format_input_file(&lines, &inputs));
+    return Ok(())
+  }
+
+  // Se revisa antes de calcular los conjuntos, que no terminan si la
+  // gramática tiene ciclos. Se revisa la gramática que escribió el usuario,
+  // antes de expandir las precedencias, para que los mensajes señalen sus
+  // líneas.
+  if mode == Mode::Lint {
+    if !run_linter(&grammar) {
+      process::exit(1);
+    }
+    return Ok(())
+  }
+
+  // También antes de expandir las precedencias, por la misma razón.
+  if mode == Mode::Reduce || arguments.has("reduce") {
+    grammar = match reduce_grammar(&grammar) {
+      Ok(reduced) => reduced,
+      Err(e) => {
+        eprintln!("{}", e);
+        process::exit(2);
+      },
+    };
+  }
+
+  if !precedences.is_empty() {
+    grammar = match expand_precedence(&grammar, &precedences) {
+      Ok(expanded) => expanded,
+      Err(e) => {
+        eprintln!("{}", e);
+        process::exit(2);
+      },
+    };
+  }
+
+  if let Mode::Reduce
+    | Mode::RemoveEpsilon
+    | Mode::RemoveUnit
+    | Mode::RemoveLeftRecursion
+    | Mode::Greibach
+    | Mode::ExpandPrecedence = mode {
+    let transformed = match mode {
+      Mode::RemoveEpsilon => remove_epsilon_productions(&grammar),
+      Mode::RemoveUnit => remove_unit_productions(&grammar),
+      Mode::RemoveLeftRecursion => remove_left_recursion(&grammar),
+      Mode::Greibach => to_greibach(&grammar).and_then(|greibach| {
+        verify_transform(&grammar, greibach, &arguments)
+      }),
+      _ => Ok(grammar),
+    };
+
+    match transformed {
+      Ok(transformed) => println!(
+        "{}", format_input_file(&transformed.production_lines(), &inputs),
+      ),
+      Err(e) => {
+        eprintln!("{}", e);
+        process::exit(2);
+      },
+    }
+    return Ok(())
+  }
+
+  grammar.find_sets();
+
+  if mode == Mode::Generate {
+    if let Err(e) = run_generator(&grammar, &arguments) {
+      eprintln!("{}", e);
+      process::exit(2);
+    }
+    return Ok(())
+  }
+
+  if mode == Mode::Enumerate {
+    if let Err(e) = run_enumeration(&grammar, &arguments) {
+      eprintln!("{}", e);
+      process::exit(2);
+    }
+    return Ok(())
+  }
+
+  if let Mode::Json | Mode::Markdown | Mode::Latex = mode {
+    let mut analyzer = LL1Analyzer::new(&mut grammar);
+    analyzer.build_table();
+    let analysis = match mode {
+      Mode::Json => analyzer.get_analysis_as_json(),
+      Mode::Markdown => analyzer.get_analysis_as_markdown(),
+      _ => analyzer.get_analysis_as_latex(),
+    };
+    println!("{}", analysis);
+    return Ok(())
+  }
+
+  if mode == Mode::Codegen {
+    let mut analyzer = LL1Analyzer::new(&mut grammar);
+    analyzer.build_table();
+    if let Err(e) = run_codegen(&analyzer, &arguments) {
+      eprintln!("{}", e);
+      process::exit(1);
+    }
+    return Ok(())
   }
 
   let is_ll1 = grammar.is_ll1();
 
   if !is_ll1 {
     println!("No se puede analizar con LL(1)");
-    return Ok(())
+    if mode == Mode::Test {
+      process::exit(1);
+    }
+    // El reporte se escribe de todos modos para mostrar los conflictos.
+    if mode != Mode::Report {
+      return Ok(())
+    }
   }
 
   let mut analyzer = LL1Analyzer::new(&mut grammar);
   analyzer.build_table();
 
+  if mode == Mode::Mutate {
+    if let Err(e) = run_mutations(&mut analyzer, &arguments) {
+      eprintln!("{}", e);
+      process::exit(2);
+    }
+    return Ok(())
+  }
+
+  if mode == Mode::Coverage {
+    if !run_coverage(&mut analyzer) {
+      process::exit(1);
+    }
+    return Ok(())
+  }
+
+  if mode == Mode::Test {
+    if !run_tests(&mut analyzer, &inputs) {
+      process::exit(1);
+    }
+    return Ok(())
+  }
+
   // Crea archivo a escribir
   let timestamp = get_timestamp();
   let mut file = OpenOptions::new()
     .append(true).create(true).open(format!("{:?}.html", timestamp)).unwrap();
 
-  // Escribe tabla a archivo
-  let table_html = analyzer.get_table_as_html();
-  if let Err(e) = writeln!(file, "{}", table_html) {
+  // Evalúa cada entrada y escribe el reporte completo al archivo HTML.
+  let report_html = analyzer.get_report_as_html(&inputs);
+  if let Err(e) = writeln!(file, "{}", report_html) {
     eprintln!("No se pudo escribir al archivo: {}", e);
   }
 
-  // Evalúa cada entrada e imprime resultados en el archivo HTML.
+  println!("El resultado está en el archivo {:?}.html", timestamp);
+
+  Ok(())
+}
+
+/// Evalúa cada entrada y compara el resultado (y la posición del error, si
+/// se indica) con el esperado en su anotación. Imprime el resultado de
+/// cada entrada y un resumen al final.
+///
+/// Regresa `true` si ninguna entrada anotada falló.
+fn run_tests(analyzer: &mut LL1Analyzer, inputs: &[Input]) -> bool {
+  let mut passed = 0;
+  let mut failed = 0;
+  let mut skipped = 0;
+
   for (index, input) in inputs.iter().enumerate() {
-    let result = analyzer.eval(&input);
+    let result = analyzer.eval(&input.text);
+    // La posición del error solo se necesita si la anotación la indica.
+    let error_position = match input.error_position {
+      Some(_) => analyzer.trace(&input.text).error_position,
+      None => None,
+    };
 
-    if let Err(e) = writeln!(
-      file,
-      "<br><b>Input #{}:</b> {}", index + 1, if result {"Yes"} else {"No"},
-    ) {
-      eprintln!("No se pudo escribir la archivo: {}", e);
+    match (input.expected, input.error_position) {
+      (Some(expected), _) if expected != result => {
+        failed += 1;
+        println!(
+          "FAIL Input #{}: {} (esperado: {}, obtenido: {})",
+          index + 1,
+          input.text,
+          result_as_annotation(expected),
+          result_as_annotation(result),
+        );
+      },
+      (Some(_), Some(expected_position)) if error_position != Some(expected_position) => {
+        failed += 1;
+        let obtained = match error_position {
+          Some(position) => format!("obtenido en la posición {}", position),
+          None => String::from("no se obtuvo la posición"),
+        };
+        println!(
+          "FAIL Input #{}: {} (error esperado en la posición {}, {})",
+          index + 1,
+          input.text,
+          expected_position,
+          obtained,
+        );
+      },
+      (Some(_), _) => {
+        passed += 1;
+        println!("PASS Input #{}: {}", index + 1, input.text);
+      },
+      (None, _) => {
+        skipped += 1;
+        println!("SKIP Input #{}: {} (sin anotación)", index + 1, input.text);
+      },
     }
   }
 
-  println!("El resultado está en el archivo {:?}.html", timestamp);
+  println!(
+    "\n{} pasaron, {} fallaron, {} sin anotación", passed, failed, skipped,
+  );
+
+  failed == 0
+}
+
+/// Genera cadenas al azar a partir del símbolo inicial de la gramática y las
+/// imprime como un archivo de entrada, anotadas como aceptadas.
+///
+/// Opciones:
+///
+/// - `--count N`: cantidad de cadenas a generar (10 por defecto).
+/// - `--seed N`: semilla para reproducir las mismas cadenas. Si no se da, se
+///   usa la hora actual y se imprime en `stderr`.
+/// - `--max-depth N`: profundidad máxima del árbol de derivación.
+/// - `--max-length N`: cantidad máxima de terminales por cadena.
+/// - `--weight I=W`: peso `W` de la producción con índice `I`. Se puede
+///   repetir.
+fn run_generator(grammar: &Grammar, arguments: &Arguments) -> Result<(), String> {
+  let seed = read_seed(arguments)?;
+  let inputs: Vec<Input> = generate_sentences(grammar, arguments, seed)?
+    .iter()
+    .map(|sentence| Input::accepted(sentence))
+    .collect();
+
+  println!("{}", format_input_file(&grammar.production_lines(), &inputs));
 
   Ok(())
 }
 
+/// Imprime como un archivo de entrada, anotadas como aceptadas, todas las
+/// cadenas que deriva la gramática con a lo más `--max-length N` terminales
+/// (5 por defecto), ordenadas por longitud. La cantidad se imprime en
+/// `stderr`.
+fn run_enumeration(grammar: &Grammar, arguments: &Arguments) -> Result<(), String> {
+  let max_length: usize = arguments.parsed("max-length", 5)?;
+  let inputs: Vec<Input> = enumerate_language(grammar, max_length)
+    .iter()
+    .map(|sentence| Input::accepted(sentence))
+    .collect();
+
+  println!("{}", format_input_file(&grammar.production_lines(), &inputs));
+  eprintln!(
+    "{} cadenas de a lo más {} terminales", inputs.len(), max_length,
+  );
+
+  Ok(())
+}
+
+/// Compara los lenguajes de las gramáticas de los dos archivos dados (solo
+/// se usan sus producciones) e imprime la cadena más corta encontrada que
+/// solo genera una de ellas.
+///
+/// Opciones:
+///
+/// - `--max-length N`: se comparan todas las cadenas de a lo más `N`
+///   terminales (6 por defecto).
+/// - `--samples N`: si coinciden, cantidad de cadenas al azar de cada
+///   gramática que se buscan en la otra (100 por defecto).
+/// - `--sample-length N`: cantidad máxima de terminales de las cadenas al
+///   azar (20 por defecto).
+/// - `--seed N`: semilla de las cadenas al azar.
+///
+/// Regresa `true` si no se encontró ninguna diferencia.
+fn run_comparison(arguments: &Arguments) -> Result<bool, String> {
+  let (first_path, second_path) = match arguments.positional.as_slice() {
+    [first, second] => (first, second),
+    _ => return Err(String::from(
+      "Uso: lexian compare <gramática1> <gramática2> [--max-length N] \
+      [--samples N] [--sample-length N] [--seed N]",
+    )),
+  };
+
+  let first = read_grammar_file(first_path)?;
+  let second = read_grammar_file(second_path)?;
+  let max_length: usize = arguments.parsed("max-length", 6)?;
+  let samples: usize = arguments.parsed("samples", 100)?;
+  let sample_length: usize = arguments.parsed("sample-length", 20)?;
+  let seed = read_seed(arguments)?;
+
+  let comparison = compare_languages(
+    &first, &second, max_length, samples, sample_length, seed,
+  );
+
+  match comparison.difference {
+    Some(difference) => {
+      let (found, missing) = if difference.in_first {
+        (first_path, second_path)
+      } else {
+        (second_path, first_path)
+      };
+      println!("Las gramáticas generan lenguajes distintos.");
+      println!(
+        "Cadena más corta encontrada: `{}` (la genera {} pero no {})",
+        difference.sentence.join(" "), found, missing,
+      );
+
+      Ok(false)
+    },
+    None => {
+      println!(
+        "Ambas gramáticas generan las mismas {} cadenas de a lo más {} \
+        terminales.",
+        comparison.enumerated, max_length,
+      );
+      println!(
+        "Las {} cadenas al azar de una gramática también las genera la otra.",
+        comparison.sampled,
+      );
+
+      Ok(true)
+    },
+  }
+}
+
+/// Imprime los problemas encontrados en la gramática, uno por línea, con la
+/// línea del archivo de entrada donde está la producción.
+///
+/// Regresa `true` si no se encontró ningún problema.
+fn run_linter(grammar: &Grammar) -> bool {
+  let lints = lint_grammar(grammar);
+
+  for lint in &lints {
+    println!("línea {}: [{}] {}", lint.line, lint.kind.name(), lint.message);
+  }
+
+  if lints.is_empty() {
+    println!("No se encontraron problemas en la gramática");
+  }
+
+  lints.is_empty()
+}
+
+/// Elimina los símbolos inútiles de la gramática e imprime en `stderr` qué
+/// se eliminó, con la línea de cada producción en el archivo de entrada.
+fn reduce_grammar(grammar: &Grammar) -> Result<Grammar, String> {
+  let reduction = remove_useless_symbols(grammar)?;
+
+  if reduction.removed.is_empty() {
+    eprintln!("La gramática no tiene símbolos inútiles");
+    return Ok(reduction.grammar);
+  }
+
+  if !reduction.unproductive.is_empty() {
+    eprintln!("No terminales improductivos: {}", reduction.unproductive.join(", "));
+  }
+  if !reduction.unreachable.is_empty() {
+    eprintln!("No terminales inalcanzables: {}", reduction.unreachable.join(", "));
+  }
+
+  eprintln!("Producciones eliminadas:");
+  for index in &reduction.removed {
+    eprintln!(
+      "  línea {}: {}", production_line(grammar, *index), grammar.production_as_string(*index),
+    );
+  }
+
+  Ok(reduction.grammar)
+}
+
+/// Compara los lenguajes de la gramática original y la transformada en
+/// todas las cadenas de a lo más `--max-length N` terminales (6 por
+/// defecto) e imprime el resultado en `stderr`.
+///
+/// Regresa la gramática transformada, o un error con la cadena más corta que
+/// las distingue.
+fn verify_transform(
+  original: &Grammar, transformed: Grammar, arguments: &Arguments,
+) -> Result<Grammar, String> {
+  let max_length: usize = arguments.parsed("max-length", 6)?;
+  let comparison = compare_languages(original, &transformed, max_length, 0, 0, 0);
+
+  match comparison.difference {
+    Some(difference) => Err(format!(
+      "La gramática transformada no es equivalente: `{}` {}",
+      difference.sentence.join(" "),
+      if difference.in_first {
+        "solo la genera la original"
+      } else {
+        "solo la genera la transformada"
+      },
+    )),
+    None => {
+      eprintln!(
+        "Verificación: ambas gramáticas generan las mismas {} cadenas de a lo \
+        más {} terminales",
+        comparison.enumerated, max_length,
+      );
+      Ok(transformed)
+    },
+  }
+}
+
+/// Genera el código del parser e imprime el resultado, o lo escribe en el
+/// archivo dado con `--output`.
+///
+/// Opciones:
+///
+/// - `--target`: lenguaje y tipo de parser. `rust` (por defecto) genera un
+///   módulo de Rust con un parser dirigido por tabla y `rust-descent` uno
+///   de descenso recursivo. `rust-ast` genera el parser dirigido por tabla
+///   y un árbol de sintaxis abstracta para una gramática anotada (ver
+///   `generate_rust_ast_parser`). `typescript` genera un módulo de
+///   TypeScript y `c` un encabezado y un código fuente de C, ambos dirigidos
+///   por tabla.
+/// - `--output ARCHIVO`: archivo donde se escribe el código. Con `c` es
+///   obligatorio: el código se escribe en `ARCHIVO` con extensión `.c` y el
+///   encabezado con extensión `.h`.
+/// - `--prefix PREFIJO`: solo con `c`, prefijo de los nombres que exporta el
+///   parser. Por defecto es el nombre de `--output` sin extensión, en
+///   minúsculas y con `_` en lugar de los caracteres que no son letras ni
+///   números.
+fn run_codegen(analyzer: &LL1Analyzer, arguments: &Arguments) -> Result<(), String> {
+  let code = match arguments.value("target").unwrap_or("rust") {
+    "rust" => generate_rust_parser(analyzer)?,
+    "rust-descent" => generate_rust_descent_parser(analyzer)?,
+    "typescript" => generate_typescript_parser(analyzer)?,
+    "c" => return run_c_codegen(analyzer, arguments),
+    other => return Err(format!(
+      "Lenguaje desconocido: {}. Lenguajes disponibles: rust, rust-descent, \
+      rust-ast, typescript, c",
+      other,
+    )),
+  };
+
+  output_code(&code, arguments)
+}
+
+/// Imprime el código generado, o lo escribe en el archivo dado con
+/// `--output`.
+fn output_code(code: &str, arguments: &Arguments) -> Result<(), String> {
+  match arguments.value("output") {
+    Some(path) => write_code(Path::new(path), code),
+    None => {
+      print!("{}", code);
+      Ok(())
+    },
+  }
+}
+
+/// Genera el parser en C y escribe el encabezado y el código fuente junto
+/// al archivo de `--output`.
+fn run_c_codegen(analyzer: &LL1Analyzer, arguments: &Arguments) -> Result<(), String> {
+  let path = match arguments.value("output") {
+    Some(path) => Path::new(path),
+    None => return Err(String::from(
+      "El lenguaje c necesita --output ARCHIVO para escribir el encabezado y \
+      el código fuente",
+    )),
+  };
+  let header_path = path.with_extension("h");
+  let source_path = path.with_extension("c");
+  let header_name = header_path
+    .file_name()
+    .map(|name| name.to_string_lossy().to_string())
+    .ok_or_else(|| format!("Nombre de archivo inválido: {}", path.display()))?;
+
+  let prefix = match arguments.value("prefix") {
+    Some(prefix) => prefix.to_owned(),
+    None => c_prefix(path),
+  };
+
+  let (header, source) = generate_c_parser(analyzer, &header_name, &prefix)?;
+  write_code(&header_path, &header)?;
+  write_code(&source_path, &source)
+}
+
+/// Regresa el prefijo por defecto del parser en C a partir del nombre del
+/// archivo: `mi-parser.c` da `mi_parser`.
+fn c_prefix(path: &Path) -> String {
+  let stem = path
+    .file_stem()
+    .map(|stem| stem.to_string_lossy().to_ascii_lowercase())
+    .unwrap_or_default();
+  let prefix: String = stem
+    .chars()
+    .map(|character| if character.is_ascii_alphanumeric() { character } else { '_' })
+    .collect();
+
+  if prefix.starts_with(|character: char| character.is_ascii_lowercase()) {
+    prefix
+  } else {
+    format!("parser_{}", prefix)
+  }
+}
+
+/// Escribe el código generado en el archivo.
+fn write_code(path: &Path, code: &str) -> Result<(), String> {
+  fs::write(path, code).map_err(|e| {
+    format!("No se pudo escribir al archivo {}: {}", path.display(), e)
+  })
+}
+
+/// Genera cadenas válidas al azar y a partir de ellas cadenas inválidas
+/// (insertando, eliminando o reemplazando un terminal), verificadas con el
+/// analizador. Imprime ambas como un archivo de entrada: las válidas
+/// anotadas como aceptadas y las inválidas como rechazadas con la posición
+/// del error.
+///
+/// Acepta las mismas opciones que `run_generator`, además de `--mutants N`:
+/// la cantidad de cadenas inválidas (por defecto, la misma que de válidas).
+fn run_mutations(
+  analyzer: &mut LL1Analyzer, arguments: &Arguments,
+) -> Result<(), String> {
+  let seed = read_seed(arguments)?;
+  let sentences = generate_sentences(analyzer.grammar, arguments, seed)?;
+  let count: usize = arguments.parsed("mutants", sentences.len())?;
+
+  let mut random = Random::new(seed.wrapping_add(1));
+  let mutants = find_mutants(analyzer, &sentences, &mut random, count);
+
+  let mut inputs: Vec<Input> = sentences
+    .iter()
+    .map(|sentence| Input::accepted(sentence))
+    .collect();
+  let (mut insertions, mut deletions, mut substitutions) = (0, 0, 0);
+
+  for mutant in &mutants {
+    match mutant.kind {
+      MutationKind::Insertion => insertions += 1,
+      MutationKind::Deletion => deletions += 1,
+      MutationKind::Substitution => substitutions += 1,
+    }
+
+    inputs.push(Input {
+      text: mutant.sentence.join(" "),
+      expected: Some(false),
+      error_position: Some(mutant.error_position),
+    });
+  }
+
+  println!(
+    "{}", format_input_file(&analyzer.grammar.production_lines(), &inputs),
+  );
+  eprintln!(
+    "{} cadenas inválidas: {} inserciones, {} eliminaciones, {} sustituciones",
+    mutants.len(), insertions, deletions, substitutions,
+  );
+
+  Ok(())
+}
+
+/// Regresa la semilla dada con `--seed`. Si no se da, se usa la hora actual
+/// y se imprime en `stderr` para poder repetir el resultado.
+fn read_seed(arguments: &Arguments) -> Result<u64, String> {
+  match arguments.value("seed") {
+    Some(_) => arguments.parsed("seed", 0),
+    None => {
+      let seed = get_timestamp().as_nanos() as u64;
+      eprintln!("Semilla: {}", seed);
+      Ok(seed)
+    },
+  }
+}
+
+/// Genera cadenas al azar con las opciones de `run_generator`.
+fn generate_sentences(
+  grammar: &Grammar, arguments: &Arguments, seed: u64,
+) -> Result<Vec<Vec<String>>, String> {
+  let count: usize = arguments.parsed("count", 10)?;
+
+  let mut generator = Generator::new(grammar, seed);
+  generator.max_depth = arguments.parsed("max-depth", generator.max_depth)?;
+  generator.max_length = arguments.parsed("max-length", generator.max_length)?;
+
+  for weight in arguments.values("weight") {
+    let invalid = || format!("Peso inválido: {}. Se esperaba índice=peso", weight);
+    let (index, value) = weight.split_once('=').ok_or_else(invalid)?;
+    let index: usize = index.parse().map_err(|_| invalid())?;
+    let value: f64 = value.parse().map_err(|_| invalid())?;
+
+    if index >= generator.weights.len() {
+      return Err(format!("No existe la producción con índice {}", index));
+    }
+    generator.weights[index] = value;
+  }
+
+  let mut sentences = vec![];
+  for _ in 0..count {
+    match generator.sentence() {
+      Some(sentence) => sentences.push(sentence),
+      None => return Err(format!(
+        "La gramática no deriva cadenas de a lo más {} terminales",
+        generator.max_length,
+      )),
+    }
+  }
+
+  Ok(sentences)
+}
+
+/// Genera las cadenas que cubren todas las casillas de la tabla de parseo y
+/// las imprime como un archivo de entrada, anotadas como aceptadas. El
+/// resumen de la cobertura se imprime en `stderr`.
+///
+/// Regresa `true` si se cubrieron todas las casillas.
+fn run_coverage(analyzer: &mut LL1Analyzer) -> bool {
+  let coverage = find_coverage(analyzer);
+  let inputs: Vec<Input> = coverage.sentences
+    .iter()
+    .map(|sentence| Input::accepted(sentence))
+    .collect();
+
+  println!(
+    "{}", format_input_file(&analyzer.grammar.production_lines(), &inputs),
+  );
+
+  eprintln!(
+    "{} cadenas cubren {} de {} casillas",
+    inputs.len(),
+    coverage.cells.len() - coverage.uncovered.len(),
+    coverage.cells.len(),
+  );
+  for (non_terminal, terminal) in &coverage.uncovered {
+    eprintln!("Sin cubrir: M[{}, {}]", non_terminal, terminal);
+  }
+
+  coverage.uncovered.is_empty()
+}
+
 /// Lee de `stdin` las producciones de la gramática libre de contexto.
 /// La entrada debe empezar con la cantidad de producciones y entradas a leer.
 /// Las producciones deben estar en la 
 /// [forma normal de Chomsky](https://en.wikipedia.org/wiki/Chomsky_normal_form)
+/// Cada entrada puede terminar con su resultado esperado (`# accept` o
+/// `# reject`).
 /// 
 /// # Ejemplo
 /// 
@@ -90,14 +847,19 @@ fn main() -> io::Result<()> {
 // A -> two
 // two -> a
 // two -> b
-// ( ( a ) )
-// ( a ) )
-// ( ( ( ( ( b ) ) ) ) )
-// ( ( ( ( ( a b ) ) ) ) )
+// ( ( a ) ) # accept
+// ( a ) ) # reject
+// ( ( ( ( ( b ) ) ) ) ) # accept
+// ( ( ( ( ( a b ) ) ) ) ) # reject
 /// ```
-pub fn read_productions() -> (Vec<String>, Vec<String>) {
-  let stdin = io::stdin();
-  let mut lines = stdin.lock().lines();
+pub fn read_productions() -> (Vec<String>, Vec<Input>) {
+  read_productions_from(io::stdin().lock())
+}
+
+/// Lee las producciones y entradas con el formato de `read_productions`
+/// desde cualquier lector.
+fn read_productions_from(reader: impl BufRead) -> (Vec<String>, Vec<Input>) {
+  let mut lines = reader.lines();
   let mut prods = Vec::new();
   let mut inputs = Vec::new();
 
@@ -127,18 +889,33 @@ pub fn read_productions() -> (Vec<String>, Vec<String>) {
           .expect("No hubo una siguiente producción")
           .expect("¡Error al leer la producción!");
 
-      // Se guarda la cadena leída
-      inputs.push(line);
+      // Se guarda la cadena leída junto con su resultado esperado
+      inputs.push(Input::parse(&line));
     }
   }
 
   (prods, inputs)
 }
 
+/// Lee la gramática de un archivo con el formato de `read_productions`. Las
+/// entradas del archivo se ignoran.
+fn read_grammar_file(path: &str) -> Result<Grammar, String> {
+  let file = File::open(path).map_err(|e| {
+    format!("No se pudo abrir el archivo {}: {}", path, e)
+  })?;
+  let (productions, _) = read_productions_from(BufReader::new(file));
+  let (precedences, grammar) = grammar_with_precedence(&productions)?;
+
+  if precedences.is_empty() {
+    Ok(grammar)
+  } else {
+    expand_precedence(&grammar, &precedences)
+  }
+}
+
 fn get_timestamp() -> Duration {
   let start = SystemTime::now();
-  let since_the_epoch = start
+  start
     .duration_since(UNIX_EPOCH)
-    .expect("Time went backwards");
-  since_the_epoch
+    .expect("Time went backwards")
 }
@@ -0,0 +1,137 @@
+use crate::grammar::{Grammar};
+use super::{Production, fresh_name, remove_empty_non_terminals, to_grammar};
+
+/// Elimina las producciones EPSILON de la gramática sin cambiar su
+/// lenguaje.
+///
+/// Cada producción se reemplaza por todas las formas que resultan de quitar
+/// cualquier combinación de sus símbolos que derivan en EPSILON. Si el
+/// símbolo inicial deriva en EPSILON, es la única producción EPSILON que se
+/// conserva; cuando además aparece del lado derecho de alguna producción, se
+/// agrega un nuevo símbolo inicial `SPrime -> S | ε` para que EPSILON no
+/// vuelva a aparecer en otras derivaciones.
+pub fn remove_epsilon_productions(grammar: &Grammar) -> Result<Grammar, String> {
+  let start = match grammar.non_terminals.first() {
+    Some(start) => start.to_owned(),
+    None => return Err(String::from("La gramática no tiene producciones")),
+  };
+  let nullables = grammar.find_nullables();
+  let mut productions: Vec<Production> = vec![];
+
+  for (index, left) in grammar.sides.left.iter().enumerate() {
+    for body in without_nullables(&grammar.production_body(index), &nullables) {
+      // `A -> A` no cambia el lenguaje.
+      let is_self_unit = body.len() == 1 && body[0] == *left;
+      let production = (left.to_owned(), body);
+
+      if !is_self_unit && !productions.contains(&production) {
+        productions.push(production);
+      }
+    }
+  }
+
+  // Los no terminales que solo derivaban en EPSILON se quedan sin
+  // producciones.
+  let mut productions = remove_empty_non_terminals(grammar, productions);
+
+  let mut new_start = start.to_owned();
+
+  if nullables.contains(&start) {
+    let in_right_side = productions
+      .iter()
+      .any(|(_, body)| body.contains(&start));
+
+    if in_right_side {
+      new_start = fresh_name(&start, &[grammar.non_terminals.as_slice(), &grammar.terminals].concat());
+      productions.insert(0, (new_start.to_owned(), vec![]));
+      productions.insert(0, (new_start.to_owned(), vec![start]));
+    } else {
+      let position = productions
+        .iter()
+        .rposition(|(left, _)| *left == start)
+        .map_or(0, |position| position + 1);
+      productions.insert(position, (start, vec![]));
+    }
+  }
+
+  if productions.first().map(|(left, _)| left) != Some(&new_start) {
+    return Err(String::from("La gramática no deriva ninguna cadena de terminales"));
+  }
+
+  Ok(to_grammar(&productions))
+}
+
+/// Regresa las formas de una producción que resultan de eliminar cualquier
+/// combinación de sus símbolos que derivan en EPSILON, sin repetir y sin la
+/// forma vacía. La producción completa es la primera.
+pub fn without_nullables(body: &[String], nullables: &[String]) -> Vec<Vec<String>> {
+  let mut forms: Vec<Vec<String>> = vec![vec![]];
+
+  for symbol in body {
+    let mut next = vec![];
+
+    for form in forms {
+      next.push([form.as_slice(), &[symbol.to_owned()]].concat());
+      if nullables.contains(symbol) {
+        next.push(form);
+      }
+    }
+
+    forms = next;
+  }
+
+  let mut unique: Vec<Vec<String>> = vec![];
+  for form in forms {
+    if !form.is_empty() && !unique.contains(&form) {
+      unique.push(form);
+    }
+  }
+
+  unique
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::examples;
+  use crate::generator::enumerate_language;
+  use super::{remove_epsilon_productions, without_nullables};
+
+  fn strings(symbols: &[&str]) -> Vec<String> {
+    symbols.iter().map(|symbol| symbol.to_string()).collect()
+  }
+
+  #[test]
+  fn forms_without_nullable_symbols() {
+    let forms = without_nullables(&strings(&["A", "b", "A"]), &strings(&["A"]));
+
+    assert_eq!(forms, vec![strings(&["A", "b", "A"]), strings(&["A", "b"]), strings(&["b", "A"]), strings(&["b"])]);
+    assert!(without_nullables(&strings(&["A"]), &strings(&["A"])) == vec![strings(&["A"])]);
+  }
+
+  #[test]
+  fn remove_epsilon_productions_of_non_terminals() {
+    let grammar = examples::unanalyzed(&["S -> A S b | c", "A -> a | ' '", "B -> S"]);
+    let result = remove_epsilon_productions(&grammar).unwrap();
+
+    assert_eq!(result.production_lines(), vec!["S -> A S b", "S -> S b", "S -> c", "A -> a", "B -> S"]);
+  }
+
+  #[test]
+  fn new_start_symbol_if_it_is_nullable_and_recursive() {
+    let grammar = examples::grammar_from(&["S -> a S b | ' '"]);
+    let result = remove_epsilon_productions(&grammar).unwrap();
+
+    assert_eq!(result.production_lines(), vec!["SPrime -> S", "SPrime -> ' '", "S -> a S b", "S -> a b"]);
+  }
+
+  #[test]
+  fn language_does_not_change() {
+    for number in &[1, 4, 6, 7] {
+      let grammar = examples::grammar(*number);
+      let result = examples::unanalyzed(&remove_epsilon_productions(&grammar).unwrap().production_lines());
+
+      assert!(result.find_nullables().len() <= 1, "input{}", number);
+      assert_eq!(enumerate_language(&result, 6), enumerate_language(&grammar, 6), "input{}", number);
+    }
+  }
+}
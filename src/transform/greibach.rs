@@ -0,0 +1,148 @@
+use crate::grammar::{Grammar};
+use super::{
+  Production, remove_epsilon_productions, remove_left_recursion, remove_unit_productions,
+  remove_useless_symbols, to_grammar,
+};
+
+/// Convierte la gramática a la forma normal de Greibach: todas las
+/// producciones empiezan con un terminal (los demás símbolos pueden ser
+/// terminales o no terminales). Si el lenguaje contiene EPSILON, la única
+/// excepción es `S -> ε` para el símbolo inicial, que no aparece del lado
+/// derecho.
+///
+/// Pasos:
+///
+/// 1. Se eliminan las producciones EPSILON, las unitarias y los símbolos
+///    inútiles.
+/// 2. Se elimina la recursión por la izquierda y se vuelven a eliminar las
+///    producciones EPSILON que agrega (`APrime -> ε`).
+/// 3. Como ya no hay recursión por la izquierda, se puede ordenar a los no
+///    terminales de forma que, al sustituir el no terminal al principio de
+///    cada producción por sus producciones (que ya empiezan con terminal),
+///    todas empiecen con un terminal.
+/// 4. Se eliminan los no terminales que quedaron inalcanzables.
+pub fn to_greibach(grammar: &Grammar) -> Result<Grammar, String> {
+  let grammar = remove_epsilon_productions(grammar)?;
+  let grammar = remove_unit_productions(&grammar)?;
+  let grammar = remove_useless_symbols(&grammar)?.grammar;
+  let grammar = remove_left_recursion(&grammar)?;
+  let grammar = remove_epsilon_productions(&grammar)?;
+
+  let mut rules: Vec<(String, Vec<Vec<String>>)> = grammar.non_terminals
+    .iter()
+    .map(|non_terminal| {
+      let bodies = grammar
+        .get_indexes_in_non_terminals(non_terminal)
+        .into_iter()
+        .map(|index| grammar.production_body(index))
+        .collect();
+      (non_terminal.to_owned(), bodies)
+    })
+    .collect();
+  let mut done: Vec<String> = vec![];
+
+  while done.len() < rules.len() {
+    let mut progress = false;
+
+    for position in 0..rules.len() {
+      let (non_terminal, bodies) = &rules[position];
+      let ready = bodies.iter().all(|body| match body.first() {
+        Some(first) if grammar.non_terminals.contains(first) => done.contains(first),
+        _ => true,
+      });
+
+      if done.contains(non_terminal) || !ready {
+        continue;
+      }
+
+      let mut substituted: Vec<Vec<String>> = vec![];
+      for body in bodies {
+        let replacements: Vec<Vec<String>> = match body.first() {
+          Some(first) if grammar.non_terminals.contains(first) => rules
+            .iter()
+            .find(|(other, _)| other == first)
+            .unwrap()
+            .1
+            .iter()
+            .map(|first_body| [first_body.as_slice(), &body[1..]].concat())
+            .collect(),
+          _ => vec![body.to_owned()],
+        };
+
+        for replacement in replacements {
+          if !substituted.contains(&replacement) {
+            substituted.push(replacement);
+          }
+        }
+      }
+
+      done.push(non_terminal.to_owned());
+      rules[position].1 = substituted;
+      progress = true;
+    }
+
+    if !progress {
+      return Err(String::from(
+        "No se pudo convertir a la forma normal de Greibach: quedó recursión \
+        por la izquierda",
+      ));
+    }
+  }
+
+  let productions: Vec<Production> = rules
+    .into_iter()
+    .flat_map(|(left, bodies)| {
+      bodies.into_iter().map(move |body| (left.to_owned(), body))
+    })
+    .collect();
+
+  Ok(remove_useless_symbols(&to_grammar(&productions))?.grammar)
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::examples;
+  use crate::generator::enumerate_language;
+  use crate::grammar::Grammar;
+  use super::to_greibach;
+
+  /// Revisa que todas las producciones empiecen con un terminal, salvo
+  /// `S -> ε` si el símbolo inicial no aparece del lado derecho.
+  fn assert_greibach(grammar: &Grammar) {
+    let start = &grammar.non_terminals[0];
+    let bodies: Vec<Vec<String>> = (0..grammar.sides.left.len())
+      .map(|index| grammar.production_body(index))
+      .collect();
+
+    for (index, body) in bodies.iter().enumerate() {
+      match body.first() {
+        Some(first) => assert!(grammar.terminals.contains(first), "{}", grammar.production_as_string(index)),
+        None => assert_eq!(&grammar.sides.left[index], start),
+      }
+    }
+
+    if bodies.iter().any(|body| body.is_empty()) {
+      assert!(bodies.iter().all(|body| !body.contains(start)));
+    }
+  }
+
+  #[test]
+  fn language_does_not_change() {
+    for number in &[1, 2, 3, 5, 7] {
+      let grammar = examples::grammar(*number);
+      let result = to_greibach(&grammar).unwrap();
+
+      assert_greibach(&result);
+      assert_eq!(enumerate_language(&result, 6), enumerate_language(&grammar, 6), "input{}", number);
+    }
+  }
+
+  #[test]
+  fn language_with_epsilon() {
+    let grammar = examples::grammar_from(&["S -> a S b | S S | ' '"]);
+    let result = to_greibach(&grammar).unwrap();
+
+    assert_greibach(&result);
+    assert_eq!(enumerate_language(&result, 6), enumerate_language(&grammar, 6));
+  }
+}
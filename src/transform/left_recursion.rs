@@ -0,0 +1,199 @@
+use crate::grammar::{Grammar};
+use super::{Production, fresh_name, to_grammar};
+
+/// Elimina la recursión por la izquierda de la gramática sin cambiar su
+/// lenguaje.
+///
+/// Se recorren los no terminales en el orden de la gramática. En las
+/// producciones de cada uno se sustituyen los no terminales anteriores que
+/// aparecen al principio, y después la recursión inmediata
+/// `A -> A α | β` se reemplaza por `A -> β APrime` y
+/// `APrime -> α APrime | ε`, igual que `EPrime` en `input1.txt`.
+///
+/// El algoritmo supone que la gramática no tiene producciones EPSILON ni
+/// ciclos A =>+ A. Si con ellos queda recursión por la izquierda oculta, se
+/// regresa un error que sugiere eliminarlos primero.
+pub fn remove_left_recursion(grammar: &Grammar) -> Result<Grammar, String> {
+  let mut taken = [grammar.non_terminals.as_slice(), &grammar.terminals].concat();
+  // Producciones ya transformadas de cada no terminal, en orden. Cada
+  // `APrime` va justo después de su no terminal.
+  let mut rules: Vec<(String, Vec<Vec<String>>)> = vec![];
+
+  for non_terminal in &grammar.non_terminals {
+    let mut bodies: Vec<Vec<String>> = grammar
+      .get_indexes_in_non_terminals(non_terminal)
+      .into_iter()
+      .map(|index| grammar.production_body(index))
+      .collect();
+
+    // Sustitución de los no terminales anteriores al principio.
+    for (previous, previous_bodies) in &rules {
+      let mut substituted: Vec<Vec<String>> = vec![];
+
+      for body in bodies {
+        let replacements = match body.first() {
+          Some(first) if first == previous => previous_bodies
+            .iter()
+            .map(|previous_body| [previous_body.as_slice(), &body[1..]].concat())
+            .collect(),
+          _ => vec![body],
+        };
+
+        for replacement in replacements {
+          if !substituted.contains(&replacement) {
+            substituted.push(replacement);
+          }
+        }
+      }
+
+      bodies = substituted;
+    }
+
+    // Recursión inmediata. `A -> A` no cambia el lenguaje.
+    let (recursive, others): (Vec<Vec<String>>, Vec<Vec<String>>) = bodies
+      .into_iter()
+      .filter(|body| !(body.len() == 1 && body[0] == *non_terminal))
+      .partition(|body| body.first() == Some(non_terminal));
+
+    if recursive.is_empty() {
+      rules.push((non_terminal.to_owned(), others));
+      continue;
+    }
+
+    if others.is_empty() {
+      return Err(format!(
+        "`{}` solo tiene producciones recursivas por la izquierda: no deriva \
+        ninguna cadena de terminales",
+        non_terminal,
+      ));
+    }
+
+    let prime = fresh_name(non_terminal, &taken);
+    taken.push(prime.to_owned());
+
+    let mut prime_bodies: Vec<Vec<String>> = recursive
+      .into_iter()
+      .map(|body| [&body[1..], &[prime.to_owned()]].concat())
+      .collect();
+    prime_bodies.push(vec![]);
+
+    rules.push((
+      non_terminal.to_owned(),
+      others
+        .into_iter()
+        .map(|body| [body, vec![prime.to_owned()]].concat())
+        .collect(),
+    ));
+    rules.push((prime, prime_bodies));
+  }
+
+  let productions: Vec<Production> = rules
+    .into_iter()
+    .flat_map(|(left, bodies)| {
+      bodies.into_iter().map(move |body| (left.to_owned(), body))
+    })
+    .collect();
+  let result = to_grammar(&productions);
+
+  match find_left_recursive(&result) {
+    Some(non_terminal) => Err(format!(
+      "No se pudo eliminar la recursión por la izquierda de `{}`. Elimine \
+      primero las producciones EPSILON y las unitarias (remove-epsilon, \
+      remove-unit)",
+      non_terminal,
+    )),
+    None => Ok(result),
+  }
+}
+
+/// Regresa el primer no terminal A tal que A =>+ A α, contando los
+/// símbolos al principio de las producciones que derivan en EPSILON.
+fn find_left_recursive(grammar: &Grammar) -> Option<String> {
+  let nullables = grammar.find_nullables();
+  // No terminales que pueden aparecer al principio de una forma derivada en
+  // un paso.
+  let left_corners = |non_terminal: &String| -> Vec<String> {
+    let mut corners = vec![];
+
+    for index in grammar.get_indexes_in_non_terminals(non_terminal) {
+      for el in grammar.production_body(index) {
+        if grammar.non_terminals.contains(&el) && !corners.contains(&el) {
+          corners.push(el.to_owned());
+        }
+        if !nullables.contains(&el) {
+          break;
+        }
+      }
+    }
+
+    corners
+  };
+
+  grammar.non_terminals.iter().find(|non_terminal| {
+    let mut reached = left_corners(non_terminal);
+    let mut current = 0;
+
+    while current < reached.len() {
+      for corner in left_corners(&reached[current]) {
+        if !reached.contains(&corner) {
+          reached.push(corner);
+        }
+      }
+      current += 1;
+    }
+
+    reached.contains(non_terminal)
+  }).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::examples;
+  use crate::generator::enumerate_language;
+  use super::remove_left_recursion;
+
+  #[test]
+  fn immediate_recursion() {
+    let result = remove_left_recursion(&examples::grammar(3)).unwrap();
+
+    assert_eq!(result.production_lines(), vec![
+      "E -> T EPrime", "EPrime -> + T EPrime", "EPrime -> ' '",
+      "T -> F TPrime", "TPrime -> * F TPrime", "TPrime -> ' '",
+      "F -> id", "F -> ( E )",
+    ]);
+  }
+
+  #[test]
+  fn indirect_recursion() {
+    let grammar = examples::unanalyzed(&["S -> A a | b", "A -> S c | d"]);
+    let result = remove_left_recursion(&grammar).unwrap();
+
+    assert_eq!(result.production_lines(), vec![
+      "S -> A a", "S -> b", "A -> b c APrime", "A -> d APrime", "APrime -> a c APrime", "APrime -> ' '",
+    ]);
+  }
+
+  #[test]
+  fn language_does_not_change() {
+    for number in &[3, 5] {
+      let grammar = examples::grammar(*number);
+      let result = remove_left_recursion(&grammar).unwrap();
+
+      assert_eq!(enumerate_language(&result, 6), enumerate_language(&grammar, 6), "input{}", number);
+    }
+  }
+
+  #[test]
+  fn hidden_recursion() {
+    let grammar = examples::unanalyzed(&["S -> A S | a", "A -> b | ' '"]);
+
+    assert!(remove_left_recursion(&grammar).err().unwrap().contains("`S`"));
+  }
+
+  #[test]
+  fn only_recursive_productions() {
+    let grammar = examples::unanalyzed(&["S -> S a"]);
+
+    assert!(remove_left_recursion(&grammar).err().unwrap().starts_with("`S` solo tiene producciones recursivas"));
+  }
+}
@@ -0,0 +1,71 @@
+mod epsilon;
+mod greibach;
+mod left_recursion;
+mod precedence;
+mod unit;
+mod useless;
+
+use crate::grammar::{Grammar};
+
+pub use epsilon::{remove_epsilon_productions, without_nullables};
+pub use greibach::to_greibach;
+pub use left_recursion::remove_left_recursion;
+pub use precedence::{
+  Associativity, Precedence, expand_precedence, grammar_with_precedence, split_precedence,
+};
+pub use unit::remove_unit_productions;
+pub use useless::remove_useless_symbols;
+
+/// Producción como no terminal y símbolos del lado derecho (vacío si es
+/// EPSILON).
+type Production = (String, Vec<String>);
+
+/// Crea una gramática a partir de las producciones, en el mismo orden. Un
+/// lado derecho vacío se escribe como EPSILON (`' '`).
+fn to_grammar(productions: &[Production]) -> Grammar {
+  Grammar::from_productions(
+    productions
+      .iter()
+      .map(|(left, body)| {
+        if body.is_empty() {
+          format!("{} -> ' '", left)
+        } else {
+          format!("{} -> {}", left, body.join(" "))
+        }
+      })
+      .collect(),
+  )
+}
+
+/// Regresa el nombre `{name}Prime` (agregando más `Prime` si hace falta) que
+/// no esté entre los nombres ocupados.
+pub fn fresh_name(name: &str, taken: &[String]) -> String {
+  let mut fresh = format!("{}Prime", name);
+
+  while taken.contains(&fresh) {
+    fresh.push_str("Prime");
+  }
+
+  fresh
+}
+
+/// Elimina las producciones que usan no terminales de la gramática original
+/// que ya no tienen producciones (y que por lo tanto se leerían como
+/// terminales), repitiendo hasta que no quede ninguna.
+fn remove_empty_non_terminals(
+  grammar: &Grammar, mut productions: Vec<Production>,
+) -> Vec<Production> {
+  loop {
+    let empty: Vec<&String> = grammar.non_terminals
+      .iter()
+      .filter(|non_terminal| productions.iter().all(|(left, _)| left != *non_terminal))
+      .collect();
+    let count = productions.len();
+
+    productions.retain(|(_, body)| body.iter().all(|el| !empty.contains(&el)));
+
+    if productions.len() == count {
+      return productions;
+    }
+  }
+}
@@ -0,0 +1,349 @@
+use crate::grammar::{Grammar};
+use super::{Production, fresh_name, to_grammar};
+
+/// Asociatividad de los operadores de un nivel de precedencia.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Associativity {
+  /// `%left`: `a - b - c` es `(a - b) - c`.
+  Left,
+  /// `%right`: `a ^ b ^ c` es `a ^ (b ^ c)`.
+  Right,
+  /// `%nonassoc`: `a == b == c` no pertenece al lenguaje.
+  NonAssoc,
+}
+
+/// Nivel de precedencia declarado con `%left`, `%right` o `%nonassoc`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Precedence {
+  pub associativity: Associativity,
+  /// Terminales que son operadores del nivel.
+  pub operators: Vec<String>,
+}
+
+impl Precedence {
+  /// Regresa la declaración como se escribe en la gramática, por ejemplo
+  /// `%left + -`.
+  pub fn declaration(&self) -> String {
+    let keyword = match self.associativity {
+      Associativity::Left => "%left",
+      Associativity::Right => "%right",
+      Associativity::NonAssoc => "%nonassoc",
+    };
+
+    format!("{} {}", keyword, self.operators.join(" "))
+  }
+}
+
+/// Separa las declaraciones de precedencia de las líneas de producciones.
+/// Cada declaración es un nivel, y los niveles van del que agrupa menos al
+/// que agrupa más, como en yacc.
+///
+/// Regresa un error si una declaración no tiene operadores, si un operador
+/// se declara más de una vez o si una línea que empieza con `|` no continúa
+/// una producción.
+pub fn split_precedence(lines: &[String]) -> Result<(Vec<Precedence>, Vec<String>), String> {
+  let mut precedences: Vec<Precedence> = vec![];
+  let mut productions = vec![];
+  // Si la línea anterior es una declaración, una línea con `|` no tiene qué
+  // continuar.
+  let mut after_declaration = false;
+
+  for line in lines {
+    let mut elements = line.split_whitespace();
+    let associativity = match elements.next() {
+      Some("%left") => Associativity::Left,
+      Some("%right") => Associativity::Right,
+      Some("%nonassoc") => Associativity::NonAssoc,
+      Some(other) if other.starts_with('%') => {
+        return Err(format!("Declaración desconocida: `{}`", line.trim()));
+      },
+      Some(other) if other.starts_with('|') && after_declaration => {
+        return Err(format!("`{}` no continúa ninguna producción", line.trim()));
+      },
+      _ => {
+        productions.push(line.to_owned());
+        after_declaration = false;
+        continue;
+      },
+    };
+    let operators: Vec<String> = elements.map(String::from).collect();
+
+    if operators.is_empty() {
+      return Err(format!("`{}` no declara ningún operador", line.trim()));
+    }
+
+    for operator in &operators {
+      let declared = precedences
+        .iter()
+        .flat_map(|precedence| &precedence.operators)
+        .chain(&operators)
+        .filter(|other| *other == operator)
+        .count();
+
+      if declared > 1 {
+        return Err(format!("El operador `{}` se declaró más de una vez", operator));
+      }
+    }
+
+    precedences.push(Precedence { associativity, operators });
+    after_declaration = true;
+  }
+
+  Ok((precedences, productions))
+}
+
+/// Separa las declaraciones de precedencia con `split_precedence` y crea la
+/// gramática con las producciones. Las líneas de las producciones
+/// (`sides.lines`) son sus índices en `lines`, contando las declaraciones,
+/// para que los mensajes que las usan señalen la línea que escribió el
+/// usuario.
+///
+/// Regresa los errores de `split_precedence` y de `Grammar::try_new`.
+pub fn grammar_with_precedence(lines: &[String]) -> Result<(Vec<Precedence>, Grammar), String> {
+  let (precedences, productions) = split_precedence(lines)?;
+  // `split_precedence` ya revisó que las líneas con `%` sean declaraciones.
+  let indexes: Vec<usize> = lines
+    .iter()
+    .enumerate()
+    .filter(|(_, line)| !line.trim_start().starts_with('%'))
+    .map(|(index, _)| index)
+    .collect();
+  let mut grammar = Grammar::try_from_productions(productions)?;
+  grammar.sides.lines = grammar.sides.lines.iter().map(|line| indexes[*line]).collect();
+
+  Ok((precedences, grammar))
+}
+
+/// Reemplaza las producciones de operadores binarios `E -> E op E`, que son
+/// ambiguas, por una gramática estratificada con un no terminal por nivel de
+/// precedencia, sin recursión por la izquierda:
+///
+/// ```text
+/// %left + -                  E -> E2 EPrime
+/// %left *                    EPrime -> + E2 EPrime | - E2 EPrime | ε
+/// E -> E + E | E - E         E2 -> E3 E2Prime
+///    | E * E | ( E ) | id    E2Prime -> * E3 E2Prime | ε
+///                            E3 -> ( E ) | id
+/// ```
+///
+/// Con `%right` el nivel queda `EPrime -> op E | ε` y con `%nonassoc`
+/// `EPrime -> op E2 | ε`. Las demás producciones de `E` pasan al último
+/// nivel; en las de un operador prefijo, como `E -> - E`, `E` se reemplaza
+/// por ese nivel, así que `- a + b` es `(- a) + b`. Los demás no terminales
+/// siguen usando `E`, que es el nivel que agrupa menos.
+///
+/// Regresa un error si un operador binario no tiene precedencia declarada o
+/// si un no terminal solo tiene producciones de operadores.
+pub fn expand_precedence(grammar: &Grammar, precedences: &[Precedence]) -> Result<Grammar, String> {
+  let mut taken = [grammar.non_terminals.as_slice(), &grammar.terminals].concat();
+  let mut productions: Vec<Production> = vec![];
+  let level_of = |operator: &String| {
+    precedences.iter().position(|precedence| precedence.operators.contains(operator))
+  };
+
+  for non_terminal in &grammar.non_terminals {
+    let bodies: Vec<Vec<String>> = grammar
+      .get_indexes_in_non_terminals(non_terminal)
+      .into_iter()
+      .map(|index| grammar.production_body(index))
+      .collect();
+    let (binary, others): (Vec<Vec<String>>, Vec<Vec<String>>) = bodies
+      .into_iter()
+      .partition(|body| match body.as_slice() {
+        [left, operator, right] => {
+          left == non_terminal && right == non_terminal && grammar.terminals.contains(operator)
+        },
+        _ => false,
+      });
+
+    if binary.is_empty() {
+      productions.extend(others.into_iter().map(|body| (non_terminal.to_owned(), body)));
+      continue;
+    }
+
+    if others.is_empty() {
+      return Err(format!(
+        "`{}` solo tiene producciones de operadores: no deriva ninguna cadena \
+        de terminales",
+        non_terminal,
+      ));
+    }
+
+    // Niveles de precedencia que usa el no terminal, con sus operadores.
+    let mut levels: Vec<(usize, Vec<String>)> = vec![];
+
+    for body in &binary {
+      let operator = &body[1];
+      let level = level_of(operator).ok_or_else(|| format!(
+        "`{} -> {}` usa el operador `{}`, que no tiene precedencia declarada \
+        (%left, %right o %nonassoc)",
+        non_terminal, body.join(" "), operator,
+      ))?;
+
+      match levels.iter_mut().find(|(other, _)| *other == level) {
+        Some((_, operators)) if !operators.contains(operator) => operators.push(operator.to_owned()),
+        Some(_) => {},
+        None => levels.push((level, vec![operator.to_owned()])),
+      }
+    }
+    levels.sort_by_key(|(level, _)| *level);
+
+    // Un no terminal por nivel, más uno para las demás producciones.
+    let mut names = vec![non_terminal.to_owned()];
+    let mut number = 2;
+
+    while names.len() <= levels.len() {
+      let name = format!("{}{}", non_terminal, number);
+      number += 1;
+
+      if !taken.contains(&name) {
+        taken.push(name.to_owned());
+        names.push(name);
+      }
+    }
+
+    for (position, (level, operators)) in levels.iter().enumerate() {
+      let current = &names[position];
+      let next = &names[position + 1];
+      let prime = fresh_name(current, &taken);
+      taken.push(prime.to_owned());
+
+      productions.push((current.to_owned(), vec![next.to_owned(), prime.to_owned()]));
+      for operator in operators {
+        let body = match precedences[*level].associativity {
+          Associativity::Left => vec![operator.to_owned(), next.to_owned(), prime.to_owned()],
+          Associativity::Right => vec![operator.to_owned(), current.to_owned()],
+          Associativity::NonAssoc => vec![operator.to_owned(), next.to_owned()],
+        };
+        productions.push((prime.to_owned(), body));
+      }
+      productions.push((prime, vec![]));
+    }
+
+    let atoms = names.last().unwrap();
+
+    for body in others {
+      let body = match body.split_last() {
+        Some((last, prefix)) if last == non_terminal
+          && !prefix.is_empty()
+          && prefix.iter().all(|el| grammar.terminals.contains(el)) => {
+          [prefix, &[atoms.to_owned()]].concat()
+        },
+        _ => body,
+      };
+
+      productions.push((atoms.to_owned(), body));
+    }
+  }
+
+  Ok(to_grammar(&productions))
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::analyzer::LL1Analyzer;
+  use crate::examples;
+  use crate::generator::enumerate_language;
+  use super::{Associativity, expand_precedence, grammar_with_precedence, split_precedence};
+
+  fn lines(text: &[&str]) -> Vec<String> {
+    text.iter().map(|line| line.to_string()).collect()
+  }
+
+  /// Expande la gramática y regresa sus producciones.
+  fn expand(text: &[&str]) -> Result<Vec<String>, String> {
+    let (precedences, grammar) = grammar_with_precedence(&lines(text))?;
+    expand_precedence(&grammar, &precedences).map(|expanded| expanded.production_lines())
+  }
+
+  #[test]
+  fn declarations_and_productions() {
+    let (precedences, productions) = split_precedence(&lines(&[
+      "%left + -", "E -> E + E | E - E", "  | E ^ E", "%right ^", "E -> id",
+    ])).unwrap();
+
+    assert_eq!(precedences.len(), 2);
+    assert_eq!(precedences[0].associativity, Associativity::Left);
+    assert_eq!(precedences[0].operators, vec!["+", "-"]);
+    assert_eq!(precedences[1].declaration(), "%right ^");
+    assert_eq!(productions, lines(&["E -> E + E | E - E", "  | E ^ E", "E -> id"]));
+  }
+
+  #[test]
+  fn invalid_declarations() {
+    assert_eq!(
+      split_precedence(&lines(&["%left"])).unwrap_err(),
+      "`%left` no declara ningún operador",
+    );
+    assert_eq!(
+      split_precedence(&lines(&["%left + -", "%right +"])).unwrap_err(),
+      "El operador `+` se declaró más de una vez",
+    );
+    assert_eq!(
+      split_precedence(&lines(&["%prec +"])).unwrap_err(),
+      "Declaración desconocida: `%prec +`",
+    );
+    assert_eq!(
+      split_precedence(&lines(&["E -> id", "%left +", "  | E + E"])).unwrap_err(),
+      "`| E + E` no continúa ninguna producción",
+    );
+  }
+
+  #[test]
+  fn lines_count_the_declarations() {
+    let (_, grammar) = grammar_with_precedence(&lines(&[
+      "%left +", "E -> E + E", "%left *", "E -> E * E | id", "  | ( E )", "X -> x",
+    ])).unwrap();
+
+    assert_eq!(grammar.sides.lines, vec![1, 3, 3, 4, 5]);
+  }
+
+  #[test]
+  fn one_level_per_declaration() {
+    let expanded = expand(&["%left + -", "%left *", "E -> E + E | E - E | E * E | ( E ) | id"]).unwrap();
+
+    assert_eq!(expanded, vec![
+      "E -> E2 EPrime", "EPrime -> + E2 EPrime", "EPrime -> - E2 EPrime", "EPrime -> ' '",
+      "E2 -> E3 E2Prime", "E2Prime -> * E3 E2Prime", "E2Prime -> ' '",
+      "E3 -> ( E )", "E3 -> id",
+    ]);
+  }
+
+  #[test]
+  fn right_and_non_associative_levels() {
+    let expanded = expand(&["%nonassoc ==", "%right ^", "E -> E == E | E ^ E | - E | id"]).unwrap();
+
+    assert_eq!(expanded, vec![
+      "E -> E2 EPrime", "EPrime -> == E2", "EPrime -> ' '",
+      "E2 -> E3 E2Prime", "E2Prime -> ^ E2", "E2Prime -> ' '",
+      "E3 -> - E3", "E3 -> id",
+    ]);
+  }
+
+  #[test]
+  fn expanded_grammar_is_ll1_and_keeps_the_language() {
+    let expanded = expand(&["%left +", "%right ^", "E -> E + E | E ^ E | ( E ) | id"]).unwrap();
+    let mut grammar = examples::grammar_from(&expanded);
+    let sentences = enumerate_language(&grammar, 3);
+    let mut analyzer = LL1Analyzer::new(&mut grammar);
+    analyzer.build_table();
+
+    assert!(analyzer.conflicts.is_empty());
+
+    assert!(sentences.iter().any(|sentence| sentence.join(" ") == "id + id"));
+    assert!(sentences.iter().any(|sentence| sentence.join(" ") == "id ^ id"));
+    assert!(sentences.iter().all(|sentence| sentence.len() % 2 == 1));
+  }
+
+  #[test]
+  fn expansion_errors() {
+    assert_eq!(
+      expand(&["%left +", "E -> E + E | E * E | id"]).unwrap_err(),
+      "`E -> E * E` usa el operador `*`, que no tiene precedencia declarada (%left, %right o %nonassoc)",
+    );
+    assert_eq!(
+      expand(&["%left +", "S -> E", "E -> E + E"]).unwrap_err(),
+      "`E` solo tiene producciones de operadores: no deriva ninguna cadena de terminales",
+    );
+  }
+}
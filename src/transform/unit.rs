@@ -0,0 +1,88 @@
+use crate::grammar::{Grammar};
+use super::{Production, remove_empty_non_terminals, to_grammar};
+
+/// Elimina las producciones unitarias `A -> B` de la gramática sin cambiar
+/// su lenguaje.
+///
+/// Para cada no terminal `A` se buscan los no terminales `B` que deriva
+/// usando solo producciones unitarias, y `A` recibe todas las producciones
+/// no unitarias de cada `B`. Las producciones quedan agrupadas por no
+/// terminal, en el orden de la gramática.
+///
+/// Las producciones como `A -> B C`, donde `C` deriva en EPSILON, no se
+/// consideran unitarias; conviene eliminar primero las producciones EPSILON.
+pub fn remove_unit_productions(grammar: &Grammar) -> Result<Grammar, String> {
+  let is_unit = |index: usize| {
+    let body = grammar.production_body(index);
+    body.len() == 1 && grammar.non_terminals.contains(&body[0])
+  };
+  let mut productions: Vec<Production> = vec![];
+
+  for non_terminal in &grammar.non_terminals {
+    // No terminales que deriva `non_terminal` con producciones unitarias,
+    // empezando por él mismo.
+    let mut reachable = vec![non_terminal.to_owned()];
+    let mut current = 0;
+
+    while current < reachable.len() {
+      for index in grammar.get_indexes_in_non_terminals(&reachable[current]) {
+        let body = grammar.production_body(index);
+        if is_unit(index) && !reachable.contains(&body[0]) {
+          reachable.push(body[0].to_owned());
+        }
+      }
+      current += 1;
+    }
+
+    for other in &reachable {
+      for index in grammar.get_indexes_in_non_terminals(other) {
+        let production = (non_terminal.to_owned(), grammar.production_body(index));
+        if !is_unit(index) && !productions.contains(&production) {
+          productions.push(production);
+        }
+      }
+    }
+  }
+
+  let productions = remove_empty_non_terminals(grammar, productions);
+
+  match productions.first() {
+    Some((left, _)) if *left == grammar.non_terminals[0] => Ok(to_grammar(&productions)),
+    _ => Err(String::from("La gramática no deriva ninguna cadena de terminales")),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::examples;
+  use crate::generator::enumerate_language;
+  use super::remove_unit_productions;
+
+  #[test]
+  fn replace_unit_productions() {
+    let grammar = examples::grammar_from(&["E -> E + T | T", "T -> F", "F -> ( E ) | id"]);
+    let result = remove_unit_productions(&grammar).unwrap();
+
+    assert_eq!(result.production_lines(), vec![
+      "E -> E + T", "E -> ( E )", "E -> id", "T -> ( E )", "T -> id", "F -> ( E )", "F -> id",
+    ]);
+  }
+
+  #[test]
+  fn unit_cycle() {
+    let grammar = examples::unanalyzed(&["S -> A | a", "A -> S | b"]);
+    let result = remove_unit_productions(&grammar).unwrap();
+
+    assert_eq!(result.production_lines(), vec!["S -> a", "S -> b", "A -> b", "A -> a"]);
+  }
+
+  #[test]
+  fn language_does_not_change() {
+    for number in &[2, 3, 5] {
+      let grammar = examples::grammar(*number);
+      let result = examples::unanalyzed(&remove_unit_productions(&grammar).unwrap().production_lines());
+
+      assert_eq!(enumerate_language(&result, 6), enumerate_language(&grammar, 6), "input{}", number);
+    }
+  }
+}
@@ -0,0 +1,135 @@
+use crate::grammar::{Grammar};
+
+/// Resultado de eliminar los símbolos inútiles de una gramática.
+pub struct Reduction {
+  /// Gramática sin símbolos inútiles.
+  pub grammar: Grammar,
+  /// No terminales eliminados porque no derivan ninguna cadena de
+  /// terminales.
+  pub unproductive: Vec<String>,
+  /// No terminales eliminados porque no se pueden alcanzar desde el símbolo
+  /// inicial (una vez eliminados los improductivos).
+  pub unreachable: Vec<String>,
+  /// Índices de las producciones eliminadas en la gramática original.
+  pub removed: Vec<usize>,
+}
+
+/// Elimina los no terminales que no derivan ninguna cadena de terminales y
+/// después los que no se pueden alcanzar desde el símbolo inicial, junto con
+/// todas las producciones en las que aparecen. El orden importa: al eliminar
+/// un no terminal improductivo otros pueden volverse inalcanzables.
+///
+/// Regresa un error si el símbolo inicial no deriva ninguna cadena, porque
+/// la gramática reducida no tendría producciones.
+pub fn remove_useless_symbols(grammar: &Grammar) -> Result<Reduction, String> {
+  let start = match grammar.non_terminals.first() {
+    Some(start) => start,
+    None => return Err(String::from("La gramática no tiene producciones")),
+  };
+  let shortest = grammar.find_shortest_derivations();
+
+  if !shortest.contains_key(start) {
+    return Err(format!(
+      "El símbolo inicial `{}` no deriva ninguna cadena de terminales", start,
+    ));
+  }
+
+  let unproductive: Vec<String> = grammar.non_terminals
+    .iter()
+    .filter(|non_terminal| !shortest.contains_key(*non_terminal))
+    .cloned()
+    .collect();
+  let productive_indexes: Vec<usize> = (0..grammar.sides.left.len())
+    .filter(|index| {
+      !unproductive.contains(&grammar.sides.left[*index])
+        && grammar.production_body(*index).iter().all(|el| !unproductive.contains(el))
+    })
+    .collect();
+
+  let productive = keep_productions(grammar, &productive_indexes);
+  let reachable = productive.find_reachable();
+  let unreachable: Vec<String> = productive.non_terminals
+    .iter()
+    .filter(|non_terminal| !reachable.contains(non_terminal))
+    .cloned()
+    .collect();
+  let kept: Vec<usize> = productive_indexes
+    .into_iter()
+    .filter(|index| reachable.contains(&grammar.sides.left[*index]))
+    .collect();
+
+  Ok(Reduction {
+    grammar: keep_productions(grammar, &kept),
+    unproductive,
+    unreachable,
+    removed: (0..grammar.sides.left.len()).filter(|index| !kept.contains(index)).collect(),
+  })
+}
+
+/// Crea una gramática solo con las producciones de los índices dados, en el
+/// mismo orden. Si la primera producción que queda no es del símbolo
+/// inicial, las producciones del símbolo inicial se mueven al principio para
+/// que siga siendo el primer no terminal.
+fn keep_productions(grammar: &Grammar, indexes: &[usize]) -> Grammar {
+  let lines = grammar.production_lines();
+  let start = &grammar.non_terminals[0];
+  let mut productions = indexes.to_owned();
+
+  if productions.first().is_some_and(|index| grammar.sides.left[*index] != *start) {
+    let (mut first, others): (Vec<usize>, Vec<usize>) = productions
+      .into_iter()
+      .partition(|index| grammar.sides.left[*index] == *start);
+    first.extend(others);
+    productions = first;
+  }
+
+  Grammar::from_productions(
+    productions.into_iter().map(|index| lines[index].to_owned()).collect(),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::examples;
+  use super::remove_useless_symbols;
+
+  #[test]
+  fn remove_unproductive_then_unreachable() {
+    let grammar = examples::grammar_from(&["S -> a | A B", "A -> a", "B -> b B", "C -> c"]);
+    let reduction = remove_useless_symbols(&grammar).unwrap();
+
+    assert_eq!(reduction.grammar.production_lines(), vec!["S -> a"]);
+    assert_eq!(reduction.unproductive, vec!["B"]);
+    assert_eq!(reduction.unreachable, vec!["A", "C"]);
+    assert_eq!(reduction.removed, vec![1, 2, 3, 4]);
+  }
+
+  #[test]
+  fn start_stays_first() {
+    let grammar = examples::grammar_from(&["S -> B", "A -> a", "S -> b A", "B -> b B"]);
+    let reduction = remove_useless_symbols(&grammar).unwrap();
+
+    assert_eq!(reduction.grammar.production_lines(), vec!["S -> b A", "A -> a"]);
+  }
+
+  #[test]
+  fn examples_do_not_change() {
+    for number in 1..=7 {
+      let grammar = examples::grammar(number);
+      let reduction = remove_useless_symbols(&grammar).unwrap();
+
+      assert_eq!(reduction.grammar.production_lines(), grammar.production_lines(), "input{}", number);
+      assert!(reduction.removed.is_empty());
+    }
+  }
+
+  #[test]
+  fn unproductive_start() {
+    let grammar = examples::grammar_from(&["S -> a S"]);
+
+    assert_eq!(
+      remove_useless_symbols(&grammar).err(),
+      Some(String::from("El símbolo inicial `S` no deriva ninguna cadena de terminales")),
+    );
+  }
+}
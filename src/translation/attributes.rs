@@ -0,0 +1,424 @@
+use std::collections::HashMap;
+
+use crate::analyzer::{LL1Analyzer, ParseTable};
+use crate::codegen::{check_ll1};
+use super::{Action, Attribute, find_production, production_as_string, productions_of, unexpected_token};
+
+/// Valor que recibe una regla semántica.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Source {
+  /// Atributo heredado del no terminal del lado izquierdo.
+  Inherited,
+  /// Símbolo del lado derecho en la posición dada, empezando en 0: el texto
+  /// si es un terminal o su atributo sintetizado si es un no terminal.
+  Child(usize),
+}
+
+/// Regla semántica junto con los valores que recibe, en orden.
+struct Rule<'t, T> {
+  sources: Vec<Source>,
+  action: Action<'t, T>,
+}
+
+/// Producción que se está reconociendo.
+struct Frame<T> {
+  production: usize,
+  /// Atributo heredado del no terminal del lado izquierdo.
+  inherited: Option<T>,
+  /// Atributos de los símbolos del lado derecho reconocidos hasta ahora.
+  children: Vec<Attribute<T>>,
+}
+
+/// Elemento de la pila del evaluador.
+enum Entry {
+  /// Símbolo del lado derecho, en la posición dada, de la producción que se
+  /// está reconociendo.
+  Child(usize),
+  /// Se terminó de reconocer el lado derecho de la producción: se calcula el
+  /// atributo sintetizado.
+  Reduce,
+}
+
+/// Gramática de atributos L-atribuida: cada no terminal tiene un atributo
+/// heredado y uno sintetizado, ambos de tipo `T`, que se calculan con reglas
+/// declaradas por producción. Por ejemplo, para que la resta sea asociativa
+/// por la izquierda:
+///
+/// ```text
+/// E -> T EPrime               EPrime.h = T.s            E.s = EPrime.s
+/// EPrime -> - T EPrime        EPrime1.h = EPrime.h - T.s
+///                             EPrime.s = EPrime1.s
+/// EPrime -> ε                 EPrime.s = EPrime.h
+/// ```
+///
+/// La regla del atributo heredado de un símbolo solo puede usar el atributo
+/// heredado del lado izquierdo y los símbolos a su izquierda, así que todos
+/// los atributos se calculan en una sola pasada de izquierda a derecha,
+/// durante el parseo predictivo.
+pub struct AttributeGrammar<'t, T> {
+  table: ParseTable,
+  /// Lado izquierdo y símbolos del lado derecho de cada producción.
+  productions: Vec<(String, Vec<String>)>,
+  start: String,
+  /// Atributo heredado del símbolo inicial.
+  start_value: Option<T>,
+  /// Reglas de los atributos heredados por producción y posición.
+  inherited: HashMap<(usize, usize), Rule<'t, T>>,
+  /// Reglas de los atributos sintetizados por producción.
+  synthesized: HashMap<usize, Rule<'t, T>>,
+}
+
+impl<'t, T: Clone> AttributeGrammar<'t, T> {
+  /// Crea una gramática de atributos sin reglas con la tabla del analizador.
+  ///
+  /// Regresa un error si la gramática no es LL(1).
+  pub fn new(analyzer: &LL1Analyzer) -> Result<Self, String> {
+    check_ll1(analyzer)?;
+
+    Ok(AttributeGrammar {
+      table: analyzer.table.clone(),
+      productions: productions_of(analyzer),
+      start: analyzer.grammar.non_terminals[0].to_owned(),
+      start_value: None,
+      inherited: HashMap::new(),
+      synthesized: HashMap::new(),
+    })
+  }
+
+  /// Asigna el atributo heredado del símbolo inicial.
+  pub fn start(&mut self, value: T) {
+    self.start_value = Some(value);
+  }
+
+  /// Declara la regla del atributo heredado del no terminal en la posición
+  /// dada del lado derecho de la producción. La regla recibe los valores de
+  /// `sources` en orden. Si ya había una regla, se reemplaza.
+  ///
+  /// Regresa un error si la gramática no tiene la producción, si en esa
+  /// posición no hay un no terminal, o si la regla usa un símbolo que no está
+  /// a la izquierda, con lo que la gramática dejaría de ser L-atribuida.
+  pub fn inherited(
+    &mut self, production: &str, position: usize, sources: &[Source],
+    rule: impl Fn(Vec<Attribute<T>>) -> T + 't,
+  ) -> Result<(), String> {
+    let index = find_production(&self.productions, production)?;
+    let body = &self.productions[index].1;
+    let name = production_as_string(&self.productions[index]);
+
+    match body.get(position) {
+      Some(symbol) if self.table.non_terminals.contains(symbol) => {},
+      _ => return Err(format!("La posición {} de `{}` no es un no terminal", position, name)),
+    }
+
+    for source in sources {
+      if let Source::Child(other) = *source {
+        if other >= body.len() {
+          return Err(format!("La posición {} no existe en `{}`", other, name));
+        }
+
+        if other >= position {
+          return Err(format!(
+            "`{}` no es L-atribuida: el atributo heredado de `{}` (posición {}) usa `{}` \
+            (posición {}), que no está a su izquierda",
+            name, body[position], position, body[other], other,
+          ));
+        }
+      }
+    }
+
+    self.inherited.insert((index, position), Rule { sources: sources.to_vec(), action: Box::new(rule) });
+    Ok(())
+  }
+
+  /// Declara la regla del atributo sintetizado del lado izquierdo de la
+  /// producción. La regla recibe los valores de `sources` en orden. Si ya
+  /// había una regla, se reemplaza.
+  ///
+  /// Regresa un error si la gramática no tiene la producción o si una
+  /// posición no existe.
+  pub fn synthesized(
+    &mut self, production: &str, sources: &[Source], rule: impl Fn(Vec<Attribute<T>>) -> T + 't,
+  ) -> Result<(), String> {
+    let index = find_production(&self.productions, production)?;
+    let body = &self.productions[index].1;
+
+    for source in sources {
+      if let Source::Child(other) = *source {
+        if other >= body.len() {
+          return Err(format!(
+            "La posición {} no existe en `{}`", other, production_as_string(&self.productions[index]),
+          ));
+        }
+      }
+    }
+
+    self.synthesized.insert(index, Rule { sources: sources.to_vec(), action: Box::new(rule) });
+    Ok(())
+  }
+
+  /// Verifica que se puedan calcular todos los atributos: cada producción
+  /// tiene regla para su atributo sintetizado, y cada no terminal cuyas
+  /// reglas usan su atributo heredado lo recibe en todas las producciones
+  /// donde aparece (o del valor inicial, si es el símbolo inicial).
+  ///
+  /// Regresa un error con un problema por línea.
+  pub fn check(&self) -> Result<(), String> {
+    let mut problems = vec![];
+
+    for (index, (left, body)) in self.productions.iter().enumerate() {
+      let name = production_as_string(&self.productions[index]);
+
+      if !self.synthesized.contains_key(&index) {
+        problems.push(format!("`{}` no tiene regla para el atributo sintetizado de `{}`", name, left));
+      }
+
+      for (position, symbol) in body.iter().enumerate() {
+        if self.uses_inherited(symbol) && !self.inherited.contains_key(&(index, position)) {
+          problems.push(format!(
+            "`{}` no tiene regla para el atributo heredado de `{}` (posición {})",
+            name, symbol, position,
+          ));
+        }
+      }
+    }
+
+    if self.uses_inherited(&self.start) && self.start_value.is_none() {
+      problems.push(format!("El símbolo inicial `{}` usa su atributo heredado, pero no tiene valor", self.start));
+    }
+
+    if problems.is_empty() {
+      Ok(())
+    } else {
+      Err(problems.join("\n"))
+    }
+  }
+
+  /// Evalúa los atributos de la entrada separándola por espacios, igual que
+  /// `LL1Analyzer::eval`, y regresa el atributo sintetizado del símbolo
+  /// inicial.
+  ///
+  /// Regresa un error si faltan reglas (ver `check`) o si la entrada no
+  /// pertenece al lenguaje.
+  pub fn translate(&self, input: &str) -> Result<T, String> {
+    self.run(input.split_whitespace().map(|text| (text, text)).collect())
+  }
+
+  /// Lo mismo que `translate`, pero con una secuencia de tokens ya
+  /// reconocidos, cada uno con su terminal y su texto.
+  pub fn translate_tokens(&self, tokens: &[(&str, &str)]) -> Result<T, String> {
+    self.run(tokens.to_vec())
+  }
+
+  /// Regresa si alguna regla de las producciones del no terminal usa su
+  /// atributo heredado.
+  fn uses_inherited(&self, non_terminal: &str) -> bool {
+    let uses = |rule: &Rule<T>| rule.sources.contains(&Source::Inherited);
+
+    self.synthesized
+      .iter()
+      .filter(|(index, _)| self.productions[**index].0 == non_terminal)
+      .any(|(_, rule)| uses(rule))
+    || self.inherited
+      .iter()
+      .filter(|((index, _), _)| self.productions[*index].0 == non_terminal)
+      .any(|(_, rule)| uses(rule))
+  }
+
+  fn run(&self, tokens: Vec<(&str, &str)>) -> Result<T, String> {
+    self.check()?;
+
+    let token = |position: usize| tokens.get(position).copied().unwrap_or(("$", "$"));
+    let mut frames: Vec<Frame<T>> = vec![];
+    let mut stack: Vec<Entry> = vec![];
+    let mut result = None;
+    let mut position = 0;
+
+    self.expand(&self.start, self.start_value.clone(), token(position), position, &mut frames, &mut stack)?;
+
+    loop {
+      let (lookahead, text) = token(position);
+      let entry = match stack.pop() {
+        Some(entry) => entry,
+        None if lookahead == "$" => return Ok(result.unwrap()),
+        None => {
+          return Err(format!(
+            "Posición {}: se encontró `{}` pero se esperaba `$`", position + 1, text,
+          ));
+        },
+      };
+
+      match entry {
+        Entry::Reduce => {
+          let frame = frames.pop().unwrap();
+          let value = evaluate(&self.synthesized[&frame.production], &frame);
+
+          match frames.last_mut() {
+            Some(parent) => parent.children.push(Attribute::Value(value)),
+            None => result = Some(value),
+          }
+        },
+        Entry::Child(index) => {
+          let frame = frames.last_mut().unwrap();
+          let symbol = &self.productions[frame.production].1[index];
+
+          if self.table.non_terminals.contains(symbol) {
+            let inherited = self.inherited
+              .get(&(frame.production, index))
+              .map(|rule| evaluate(rule, frame));
+
+            self.expand(symbol, inherited, (lookahead, text), position, &mut frames, &mut stack)?;
+            continue;
+          }
+
+          if lookahead != symbol {
+            return Err(format!(
+              "Posición {}: se encontró `{}` pero se esperaba `{}`", position + 1, text, symbol,
+            ));
+          }
+
+          frame.children.push(Attribute::Token(text.to_owned()));
+          position += 1;
+        },
+      }
+    }
+  }
+
+  /// Empieza a reconocer el no terminal con la producción que indica la
+  /// tabla para el token.
+  fn expand(
+    &self, non_terminal: &str, inherited: Option<T>, (lookahead, text): (&str, &str),
+    position: usize, frames: &mut Vec<Frame<T>>, stack: &mut Vec<Entry>,
+  ) -> Result<(), String> {
+    let production = match self.table.get(non_terminal, lookahead) {
+      Some(production) => production,
+      None => return Err(unexpected_token(&self.table, non_terminal, position, text)),
+    };
+
+    frames.push(Frame { production, inherited, children: vec![] });
+    stack.push(Entry::Reduce);
+    for index in (0..self.productions[production].1.len()).rev() {
+      stack.push(Entry::Child(index));
+    }
+
+    Ok(())
+  }
+}
+
+/// Ejecuta la regla con los valores que recibe, tomados de la producción que
+/// se está reconociendo.
+fn evaluate<T: Clone>(rule: &Rule<T>, frame: &Frame<T>) -> T {
+  let values = rule.sources
+    .iter()
+    .map(|source| match source {
+      Source::Inherited => Attribute::Value(
+        frame.inherited.clone().expect("el no terminal no recibió su atributo heredado"),
+      ),
+      Source::Child(index) => frame.children[*index].clone(),
+    })
+    .collect();
+
+  (rule.action)(values)
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::analyzer::LL1Analyzer;
+  use crate::examples;
+  use super::{AttributeGrammar, Source};
+
+  const SUBTRACTION: [&str; 5] = [
+    "E -> T EPrime", "EPrime -> - T EPrime", "EPrime -> ' '", "T -> ( E )", "T -> num",
+  ];
+
+  /// Resta asociativa por la izquierda con la gramática `SUBTRACTION`.
+  fn subtraction(analyzer: &LL1Analyzer) -> AttributeGrammar<'static, i64> {
+    let mut subtraction = AttributeGrammar::new(analyzer).unwrap();
+    subtraction.inherited("E -> T EPrime", 1, &[Source::Child(0)], |a| *a[0].value()).unwrap();
+    subtraction.synthesized("E -> T EPrime", &[Source::Child(1)], |a| *a[0].value()).unwrap();
+    subtraction.inherited(
+      "EPrime -> - T EPrime", 2, &[Source::Inherited, Source::Child(1)],
+      |a| a[0].value() - a[1].value(),
+    ).unwrap();
+    subtraction.synthesized("EPrime -> - T EPrime", &[Source::Child(2)], |a| *a[0].value()).unwrap();
+    subtraction.synthesized("EPrime -> ε", &[Source::Inherited], |a| *a[0].value()).unwrap();
+    subtraction.synthesized("T -> ( E )", &[Source::Child(1)], |a| *a[0].value()).unwrap();
+    subtraction.synthesized("T -> num", &[Source::Child(0)], |a| a[0].text().parse().unwrap()).unwrap();
+    subtraction
+  }
+
+  #[test]
+  fn subtraction_is_left_associative() {
+    let mut grammar = examples::grammar_from(&SUBTRACTION);
+    let mut analyzer = LL1Analyzer::new(&mut grammar);
+    analyzer.build_table();
+    let subtraction = subtraction(&analyzer);
+
+    let tokens = [("num", "10"), ("-", "-"), ("num", "3"), ("-", "-"), ("num", "2")];
+    assert_eq!(subtraction.translate_tokens(&tokens), Ok(5));
+
+    let tokens = [
+      ("num", "10"), ("-", "-"), ("(", "("), ("num", "3"), ("-", "-"), ("num", "2"), (")", ")"),
+    ];
+    assert_eq!(subtraction.translate_tokens(&tokens), Ok(9));
+  }
+
+  #[test]
+  fn rules_that_are_not_l_attributed() {
+    let mut grammar = examples::grammar_from(&SUBTRACTION);
+    let mut analyzer = LL1Analyzer::new(&mut grammar);
+    analyzer.build_table();
+    let mut subtraction = subtraction(&analyzer);
+
+    assert_eq!(
+      subtraction.inherited("EPrime -> - T EPrime", 2, &[Source::Child(2)], |a| *a[0].value()),
+      Err(String::from(
+        "`EPrime -> - T EPrime` no es L-atribuida: el atributo heredado de `EPrime` (posición 2) \
+        usa `EPrime` (posición 2), que no está a su izquierda"
+      )),
+    );
+    assert_eq!(
+      subtraction.inherited("EPrime -> - T EPrime", 0, &[], |_| 0),
+      Err(String::from("La posición 0 de `EPrime -> - T EPrime` no es un no terminal")),
+    );
+    assert_eq!(
+      subtraction.synthesized("T -> num", &[Source::Child(1)], |_| 0),
+      Err(String::from("La posición 1 no existe en `T -> num`")),
+    );
+  }
+
+  #[test]
+  fn missing_rules() {
+    let mut grammar = examples::grammar_from(&SUBTRACTION);
+    let mut analyzer = LL1Analyzer::new(&mut grammar);
+    analyzer.build_table();
+    let mut attributes: AttributeGrammar<i64> = AttributeGrammar::new(&analyzer).unwrap();
+    attributes.synthesized("EPrime -> ε", &[Source::Inherited], |a| *a[0].value()).unwrap();
+
+    let problems = attributes.check().unwrap_err();
+    assert!(problems.contains("`E -> T EPrime` no tiene regla para el atributo sintetizado de `E`"));
+    assert!(problems.contains(
+      "`E -> T EPrime` no tiene regla para el atributo heredado de `EPrime` (posición 1)"
+    ));
+    assert_eq!(attributes.translate("num"), Err(problems));
+  }
+
+  #[test]
+  fn inherited_attribute_of_the_start_symbol() {
+    let mut grammar = examples::grammar_from(&["S -> a S", "S -> ' '"]);
+    let mut analyzer = LL1Analyzer::new(&mut grammar);
+    analyzer.build_table();
+    // Cuenta las `a` a partir del valor inicial.
+    let mut count: AttributeGrammar<i64> = AttributeGrammar::new(&analyzer).unwrap();
+    count.inherited("S -> a S", 1, &[Source::Inherited], |a| a[0].value() + 1).unwrap();
+    count.synthesized("S -> a S", &[Source::Child(1)], |a| *a[0].value()).unwrap();
+    count.synthesized("S -> ε", &[Source::Inherited], |a| *a[0].value()).unwrap();
+
+    assert_eq!(
+      count.check(),
+      Err(String::from("El símbolo inicial `S` usa su atributo heredado, pero no tiene valor")),
+    );
+
+    count.start(10);
+    assert_eq!(count.translate("a a a"), Ok(13));
+  }
+}
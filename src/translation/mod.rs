@@ -0,0 +1,327 @@
+mod attributes;
+
+use std::collections::HashMap;
+
+use crate::analyzer::{LL1Analyzer, ParseTable};
+use crate::codegen::{check_ll1};
+
+pub use attributes::{AttributeGrammar, Source};
+
+/// Atributo de un símbolo del lado derecho de una producción.
+#[derive(Clone)]
+pub enum Attribute<T> {
+  /// Texto del token de un terminal.
+  Token(String),
+  /// Atributo sintetizado de un no terminal.
+  Value(T),
+}
+
+impl<T> Attribute<T> {
+  /// Regresa el texto del token.
+  ///
+  /// # Panics
+  ///
+  /// Si el atributo es de un no terminal. La acción de una producción sabe
+  /// qué símbolo hay en cada posición de su lado derecho, así que el pánico
+  /// indica un error en la acción.
+  pub fn text(&self) -> &str {
+    match self {
+      Attribute::Token(text) => text,
+      Attribute::Value(_) => panic!("el atributo es de un no terminal, no de un token"),
+    }
+  }
+
+  /// Regresa el atributo sintetizado.
+  ///
+  /// # Panics
+  ///
+  /// Si el atributo es de un terminal, igual que `text` con un no terminal.
+  pub fn value(&self) -> &T {
+    match self {
+      Attribute::Value(value) => value,
+      Attribute::Token(text) => panic!("el atributo es del token `{}`, no de un no terminal", text),
+    }
+  }
+
+  /// Lo mismo que `value`, pero regresa el atributo en lugar de una
+  /// referencia.
+  ///
+  /// # Panics
+  ///
+  /// Si el atributo es de un terminal.
+  pub fn into_value(self) -> T {
+    match self {
+      Attribute::Value(value) => value,
+      Attribute::Token(text) => panic!("el atributo es del token `{}`, no de un no terminal", text),
+    }
+  }
+}
+
+/// Acción semántica de una producción: recibe los atributos de los símbolos
+/// del lado derecho, en orden, y regresa el atributo sintetizado del no
+/// terminal del lado izquierdo.
+pub type Action<'t, T> = Box<dyn Fn(Vec<Attribute<T>>) -> T + 't>;
+
+/// Elemento de la pila del traductor.
+enum Entry {
+  /// Símbolo por derivar.
+  Symbol(String),
+  /// Se terminó de reconocer el lado derecho de la producción: se ejecuta su
+  /// acción.
+  Reduce(usize),
+}
+
+/// Traductor dirigido por la sintaxis: el parser predictivo de la tabla
+/// LL(1) que, al terminar de reconocer el lado derecho de cada producción,
+/// ejecuta su acción semántica con los atributos de sus símbolos. El
+/// atributo sintetizado del símbolo inicial es el resultado de la
+/// traducción.
+pub struct Translator<'t, T> {
+  table: ParseTable,
+  /// Lado izquierdo y símbolos del lado derecho de cada producción.
+  productions: Vec<(String, Vec<String>)>,
+  start: String,
+  actions: HashMap<usize, Action<'t, T>>,
+}
+
+impl<'t, T> Translator<'t, T> {
+  /// Crea un traductor sin acciones con la tabla del analizador.
+  ///
+  /// Regresa un error si la gramática no es LL(1).
+  pub fn new(analyzer: &LL1Analyzer) -> Result<Self, String> {
+    check_ll1(analyzer)?;
+
+    Ok(Translator {
+      table: analyzer.table.clone(),
+      productions: productions_of(analyzer),
+      start: analyzer.grammar.non_terminals[0].to_owned(),
+      actions: HashMap::new(),
+    })
+  }
+
+  /// Asocia la acción a la producción, escrita como en la gramática, por
+  /// ejemplo `E -> T EPrime` o `EPrime -> ε`. Si la producción ya tenía una
+  /// acción, se reemplaza.
+  ///
+  /// Regresa un error si la gramática no tiene la producción.
+  pub fn action(
+    &mut self, production: &str, action: impl Fn(Vec<Attribute<T>>) -> T + 't,
+  ) -> Result<(), String> {
+    let index = find_production(&self.productions, production)?;
+
+    self.actions.insert(index, Box::new(action));
+    Ok(())
+  }
+
+  /// Traduce la entrada separándola por espacios, igual que
+  /// `LL1Analyzer::eval`: el texto de cada token es el terminal.
+  ///
+  /// Regresa un error si la entrada no pertenece al lenguaje o si se usa una
+  /// producción sin acción.
+  pub fn translate(&self, input: &str) -> Result<T, String> {
+    self.run(input.split_whitespace().map(|text| (text, text)).collect())
+  }
+
+  /// Traduce una secuencia de tokens ya reconocidos, cada uno con su
+  /// terminal y su texto, por ejemplo `("id", "42")`.
+  pub fn translate_tokens(&self, tokens: &[(&str, &str)]) -> Result<T, String> {
+    self.run(tokens.to_vec())
+  }
+
+  fn run(&self, tokens: Vec<(&str, &str)>) -> Result<T, String> {
+    let mut stack = vec![Entry::Symbol(self.start.to_owned())];
+    let mut attributes: Vec<Attribute<T>> = vec![];
+    let mut position = 0;
+
+    loop {
+      let (lookahead, text) = tokens.get(position).copied().unwrap_or(("$", "$"));
+      let entry = match stack.pop() {
+        Some(entry) => entry,
+        None if lookahead == "$" => return Ok(attributes.pop().unwrap().into_value()),
+        None => {
+          return Err(format!(
+            "Posición {}: se encontró `{}` pero se esperaba `$`", position + 1, text,
+          ));
+        },
+      };
+
+      match entry {
+        Entry::Reduce(index) => {
+          let action = self.actions.get(&index).ok_or_else(|| {
+            format!("La producción `{}` no tiene acción", production_as_string(&self.productions[index]))
+          })?;
+          let children = attributes.split_off(attributes.len() - self.productions[index].1.len());
+
+          attributes.push(Attribute::Value(action(children)));
+        },
+        Entry::Symbol(symbol) if self.table.non_terminals.contains(&symbol) => {
+          let index = match self.table.get(&symbol, lookahead) {
+            Some(index) => index,
+            None => return Err(unexpected_token(&self.table, &symbol, position, text)),
+          };
+
+          stack.push(Entry::Reduce(index));
+          for el in self.productions[index].1.iter().rev() {
+            stack.push(Entry::Symbol(el.to_owned()));
+          }
+        },
+        Entry::Symbol(terminal) => {
+          if lookahead != terminal {
+            return Err(format!(
+              "Posición {}: se encontró `{}` pero se esperaba `{}`", position + 1, text, terminal,
+            ));
+          }
+
+          attributes.push(Attribute::Token(text.to_owned()));
+          position += 1;
+        },
+      }
+    }
+  }
+}
+
+/// Regresa el lado izquierdo y los símbolos del lado derecho de cada
+/// producción de la gramática del analizador.
+fn productions_of(analyzer: &LL1Analyzer) -> Vec<(String, Vec<String>)> {
+  let grammar = &analyzer.grammar;
+
+  (0..grammar.sides.left.len())
+    .map(|index| (grammar.sides.left[index].to_owned(), grammar.production_body(index)))
+    .collect()
+}
+
+/// Regresa el índice de la producción escrita como en la gramática, por
+/// ejemplo `E -> T EPrime` o `EPrime -> ε`.
+fn find_production(productions: &[(String, Vec<String>)], production: &str) -> Result<usize, String> {
+  let (left, body) = match production.split_once("->") {
+    Some((left, right)) => {
+      let body: Vec<String> = right
+        .split_whitespace()
+        .filter(|el| *el != "ε")
+        .map(String::from)
+        .collect();
+
+      (left.trim(), body)
+    },
+    None => return Err(format!("`{}` no es una producción", production)),
+  };
+  // `' '` se separa en dos elementos.
+  let body = match body.as_slice() {
+    [first, second] if first == "'" && second == "'" => vec![],
+    _ => body,
+  };
+
+  productions
+    .iter()
+    .position(|(other_left, other_body)| other_left == left && *other_body == body)
+    .ok_or_else(|| format!("La gramática no tiene la producción `{}`", production))
+}
+
+/// Regresa la producción como en la gramática.
+fn production_as_string((left, body): &(String, Vec<String>)) -> String {
+  if body.is_empty() {
+    format!("{} -> ε", left)
+  } else {
+    format!("{} -> {}", left, body.join(" "))
+  }
+}
+
+/// Regresa el error de sintaxis cuando no hay producción del no terminal
+/// para el token, con los terminales que se esperaban.
+fn unexpected_token(table: &ParseTable, non_terminal: &str, position: usize, text: &str) -> String {
+  let expected: Vec<&str> = table.row(non_terminal)
+    .into_iter()
+    .map(|(terminal, _)| terminal.as_str())
+    .collect();
+
+  format!(
+    "Posición {}: se encontró `{}` pero se esperaba uno de: {}",
+    position + 1, text, expected.join(" "),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::analyzer::LL1Analyzer;
+  use crate::examples;
+  use super::{Attribute, Translator};
+
+  /// Calculadora con la gramática de `input1.txt`.
+  fn calculator(analyzer: &LL1Analyzer) -> Translator<'static, f64> {
+    let mut calculator = Translator::new(analyzer).unwrap();
+    calculator.action("E -> T EPrime", |a| a[0].value() + a[1].value()).unwrap();
+    calculator.action("EPrime -> + T EPrime", |a| a[1].value() + a[2].value()).unwrap();
+    calculator.action("EPrime -> ε", |_| 0.0).unwrap();
+    calculator.action("T -> F TPrime", |a| a[0].value() * a[1].value()).unwrap();
+    calculator.action("TPrime -> * F TPrime", |a| a[1].value() * a[2].value()).unwrap();
+    calculator.action("TPrime -> ' '", |_| 1.0).unwrap();
+    calculator.action("F -> ( E )", |a| *a[1].value()).unwrap();
+    calculator.action("F -> id", |a| a[0].text().parse().unwrap()).unwrap();
+    calculator
+  }
+
+  #[test]
+  fn calculator_of_input1() {
+    let mut grammar = examples::grammar(1);
+    let mut analyzer = LL1Analyzer::new(&mut grammar);
+    analyzer.build_table();
+    let calculator = calculator(&analyzer);
+
+    // 2 + 3 * ( 4 + 1 ) = 17
+    let tokens = [
+      ("id", "2"), ("+", "+"), ("id", "3"), ("*", "*"),
+      ("(", "("), ("id", "4"), ("+", "+"), ("id", "1"), (")", ")"),
+    ];
+    assert_eq!(calculator.translate_tokens(&tokens), Ok(17.0));
+    assert_eq!(calculator.translate_tokens(&[("id", "2"), ("*", "*"), ("id", "4")]), Ok(8.0));
+  }
+
+  #[test]
+  fn syntax_errors() {
+    let mut grammar = examples::grammar(1);
+    let mut analyzer = LL1Analyzer::new(&mut grammar);
+    analyzer.build_table();
+    let calculator = calculator(&analyzer);
+
+    assert_eq!(
+      calculator.translate_tokens(&[("id", "2"), ("*", "*"), ("*", "*"), ("id", "4")]),
+      Err(String::from("Posición 3: se encontró `*` pero se esperaba uno de: ( id")),
+    );
+    assert_eq!(
+      calculator.translate_tokens(&[("id", "2"), (")", ")")]),
+      Err(String::from("Posición 2: se encontró `)` pero se esperaba `$`")),
+    );
+  }
+
+  #[test]
+  fn missing_action_and_production() {
+    let mut grammar = examples::grammar(1);
+    let mut analyzer = LL1Analyzer::new(&mut grammar);
+    analyzer.build_table();
+    let mut translator: Translator<f64> = Translator::new(&analyzer).unwrap();
+
+    assert_eq!(
+      translator.action("F -> num", |_| 0.0),
+      Err(String::from("La gramática no tiene la producción `F -> num`")),
+    );
+    assert_eq!(
+      translator.translate("id"),
+      Err(String::from("La producción `F -> id` no tiene acción")),
+    );
+  }
+
+  #[test]
+  fn non_ll1_grammar() {
+    let mut grammar = examples::grammar(4);
+    let mut analyzer = LL1Analyzer::new(&mut grammar);
+    analyzer.build_table();
+
+    assert!(Translator::<f64>::new(&analyzer).is_err());
+  }
+
+  #[test]
+  #[should_panic(expected = "el atributo es del token `id`")]
+  fn value_of_a_token() {
+    Attribute::<f64>::Token(String::from("id")).value();
+  }
+}
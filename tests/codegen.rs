@@ -0,0 +1,356 @@
+//! Genera los parsers de cada lenguaje para `input1.txt`, los compila y
+//! verifica que acepten y rechacen las entradas del archivo como indican
+//! sus anotaciones.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+
+use lexian::input::Input;
+
+const EXAMPLE: &str = include_str!("../src/examples/input1.txt");
+
+/// Programa de Rust que analiza cada línea de la entrada estándar con el
+/// parser generado en `parser.rs` e imprime `accept` o `reject`.
+const RUST_DRIVER: &str = r#"
+#[allow(dead_code)]
+mod parser;
+
+use std::io::BufRead;
+
+fn main() {
+    for line in std::io::stdin().lock().lines() {
+        let line = line.unwrap();
+        println!("{}", if parser::parse(&line).is_ok() { "accept" } else { "reject" });
+    }
+}
+"#;
+
+/// Programa de Rust que imprime los eventos que recibe el `Visitor` del
+/// parser generado al analizar cada línea de la entrada estándar.
+const VISITOR_DRIVER: &str = r#"
+#[allow(dead_code)]
+mod parser;
+
+use std::io::BufRead;
+
+struct Events;
+
+impl parser::Visitor for Events {
+    fn enter(&mut self, non_terminal: parser::NonTerminal, production: usize) {
+        println!("enter {:?} {}", non_terminal, production);
+    }
+
+    fn token(&mut self, token: &parser::Token) {
+        println!("token {}", token.text);
+    }
+
+    fn exit(&mut self, non_terminal: parser::NonTerminal, production: usize) {
+        println!("exit {:?} {}", non_terminal, production);
+    }
+
+    fn error(&mut self, error: &parser::ParseError) {
+        println!("error {}", error.position);
+    }
+}
+
+fn main() {
+    for line in std::io::stdin().lock().lines() {
+        let _ = parser::parse_with(&line.unwrap(), &mut Events);
+    }
+}
+"#;
+
+/// Eventos de `VISITOR_DRIVER` con las entradas `id * id` y `id )`.
+const VISITOR_EVENTS: &str = "\
+enter E 0
+enter T 3
+enter F 7
+token id
+exit F 7
+enter TPrime 4
+token *
+enter F 7
+token id
+exit F 7
+enter TPrime 5
+exit TPrime 5
+exit TPrime 4
+exit T 3
+enter EPrime 2
+exit EPrime 2
+exit E 0
+enter E 0
+enter T 3
+enter F 7
+token id
+exit F 7
+enter TPrime 5
+exit TPrime 5
+exit T 3
+enter EPrime 2
+exit EPrime 2
+exit E 0
+error 2
+";
+
+/// Gramática anotada para `--target rust-ast`.
+const ANNOTATED: &str = "\
+5 0
+E -> E + T => Add(left, _, right)
+  | E - T => Sub(left, _, right)
+  | T => Term(term)
+T -> ( E ) => Group(_, inner, _)
+  | id => Id(name)
+";
+
+/// Programa de Rust que imprime el árbol de sintaxis abstracta de cada
+/// línea de la entrada estándar, con los nodos entre paréntesis.
+const AST_DRIVER: &str = r#"
+#[allow(dead_code)]
+mod parser;
+
+use std::io::BufRead;
+
+use parser::ast::{E, T};
+
+fn e(ast: &E) -> String {
+    match ast {
+        E::Add { left, right } => format!("({} + {})", e(left), t(right)),
+        E::Sub { left, right } => format!("({} - {})", e(left), t(right)),
+        E::Term { term } => t(term),
+    }
+}
+
+fn t(ast: &T) -> String {
+    match ast {
+        T::Group { inner } => e(inner),
+        T::Id { name } => name.text.clone(),
+    }
+}
+
+fn main() {
+    for line in std::io::stdin().lock().lines() {
+        match parser::parse_ast(&line.unwrap()) {
+            Ok(ast) => println!("{}", e(&ast)),
+            Err(error) => println!("{}", error),
+        }
+    }
+}
+"#;
+
+/// Programa de C que hace lo mismo con el parser de `expr.h`, enlazado con
+/// el de otra gramática (`other.h`) para verificar que los nombres no
+/// choquen.
+const C_DRIVER: &str = r#"
+#include <stdio.h>
+#include <string.h>
+
+#include "expr.h"
+#include "other.h"
+
+int main(void) {
+    char line[1024];
+    OtherParseError other_error;
+    OtherTree *other = other_parse("", &other_error);
+
+    if (other != NULL) {
+        other_tree_free(other);
+    } else {
+        other_parse_error_free(&other_error);
+    }
+
+    while (fgets(line, sizeof line, stdin) != NULL) {
+        ExprParseError error;
+        ExprTree *tree;
+
+        line[strcspn(line, "\n")] = '\0';
+        tree = expr_parse(line, &error);
+
+        if (tree != NULL) {
+            expr_tree_free(tree);
+            puts("accept");
+        } else {
+            expr_parse_error_free(&error);
+            puts("reject");
+        }
+    }
+
+    return 0;
+}
+"#;
+
+/// Programa de TypeScript que hace lo mismo con el parser de `parser.ts`.
+const TYPESCRIPT_DRIVER: &str = r#"
+import { parse } from "./parser";
+
+declare const require: (name: string) => any;
+
+const lines: string[] = require("fs").readFileSync(0, "utf8").split("\n");
+
+for (const line of lines.slice(0, lines.length - 1)) {
+    try {
+        parse(line);
+        console.log("accept");
+    } catch (error) {
+        console.log("reject");
+    }
+}
+"#;
+
+/// Regresa las entradas anotadas de `input1.txt`.
+fn inputs() -> Vec<Input> {
+  let productions: usize = EXAMPLE.split_whitespace().next().unwrap().parse().unwrap();
+
+  EXAMPLE
+    .lines()
+    .skip(1 + productions)
+    .map(Input::parse)
+    .filter(|input| input.expected.is_some())
+    .collect()
+}
+
+/// Crea un directorio vacío para los archivos de una prueba.
+fn directory(name: &str) -> PathBuf {
+  let path = std::env::temp_dir().join(format!("lexian-{}-{}", name, std::process::id()));
+  let _ = fs::remove_dir_all(&path);
+  fs::create_dir_all(&path).unwrap();
+  path
+}
+
+/// Ejecuta el programa, pasándole `input` por la entrada estándar.
+fn run(command: &mut Command, input: &str) -> Output {
+  let mut child = command
+    .stdin(Stdio::piped())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()
+    .unwrap();
+  child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+  let output = child.wait_with_output().unwrap();
+
+  assert!(
+    output.status.success(),
+    "{:?} falló:\n{}", command, String::from_utf8_lossy(&output.stderr),
+  );
+  output
+}
+
+/// Genera el parser con `lexian codegen` y los argumentos dados.
+fn codegen(grammar: &str, arguments: &[&str]) -> Output {
+  run(Command::new(env!("CARGO_BIN_EXE_lexian")).arg("codegen").args(arguments), grammar)
+}
+
+/// Verifica que el programa acepte y rechace las entradas de `input1.txt`
+/// como indican sus anotaciones.
+fn check_results(command: &mut Command) {
+  let inputs = inputs();
+  let text: String = inputs.iter().map(|input| format!("{}\n", input.text)).collect();
+  let output = run(command, &text);
+  let results: Vec<bool> = String::from_utf8(output.stdout)
+    .unwrap()
+    .lines()
+    .map(|line| line == "accept")
+    .collect();
+  let expected: Vec<bool> = inputs.iter().map(|input| input.expected.unwrap()).collect();
+
+  assert_eq!(results, expected);
+}
+
+/// Compila el parser de Rust generado con `target` para la gramática junto
+/// con el programa dado. Regresa la ruta del ejecutable.
+fn compile_rust(grammar: &str, target: &str, name: &str, driver: &str) -> PathBuf {
+  let path = directory(name);
+  let code = codegen(grammar, &["--target", target]).stdout;
+  fs::write(path.join("parser.rs"), code).unwrap();
+  fs::write(path.join("main.rs"), driver).unwrap();
+
+  let compilation = Command::new("rustc")
+    .args(["--edition", "2018", "-D", "warnings", "main.rs", "-o", "main"])
+    .current_dir(&path)
+    .output()
+    .unwrap();
+  assert!(compilation.status.success(), "{}", String::from_utf8_lossy(&compilation.stderr));
+
+  path.join("main")
+}
+
+/// Verifica los eventos que recibe el `Visitor` del parser de Rust generado
+/// con `target`.
+fn check_visitor(target: &str) {
+  let program = compile_rust(EXAMPLE, target, &format!("{}-visitor", target), VISITOR_DRIVER);
+  let output = run(&mut Command::new(program), "id * id\nid )\n");
+
+  assert_eq!(String::from_utf8(output.stdout).unwrap(), VISITOR_EVENTS);
+}
+
+#[test]
+fn rust_parser() {
+  check_results(&mut Command::new(compile_rust(EXAMPLE, "rust", "rust", RUST_DRIVER)));
+}
+
+#[test]
+fn rust_descent_parser() {
+  check_results(&mut Command::new(compile_rust(EXAMPLE, "rust-descent", "rust-descent", RUST_DRIVER)));
+}
+
+#[test]
+fn rust_visitor_events() {
+  check_visitor("rust");
+}
+
+#[test]
+fn rust_descent_visitor_events() {
+  check_visitor("rust-descent");
+}
+
+#[test]
+fn rust_ast_parser() {
+  let program = compile_rust(ANNOTATED, "rust-ast", "rust-ast", AST_DRIVER);
+  let output = run(&mut Command::new(program), "id - id - id\nid - ( id - id ) + id\nid -\n");
+
+  assert_eq!(
+    String::from_utf8(output.stdout).unwrap(),
+    "((id - id) - id)\n((id - (id - id)) + id)\nPosición 3: se encontró `$` pero se esperaba uno de: ( id\n",
+  );
+}
+
+#[test]
+fn c_parser() {
+  let path = directory("c");
+  let other = "1 0\nS -> a S b\n";
+  codegen(EXAMPLE, &["--target", "c", "--output", path.join("expr.c").to_str().unwrap()]);
+  codegen(other, &["--target", "c", "--output", path.join("other.c").to_str().unwrap()]);
+  fs::write(path.join("main.c"), C_DRIVER).unwrap();
+
+  let compilation = Command::new("cc")
+    .args(["-std=c99", "-Wall", "-Wextra", "-pedantic", "-Werror"])
+    .args(["main.c", "expr.c", "other.c", "-o", "main"])
+    .current_dir(&path)
+    .output()
+    .unwrap();
+  assert!(compilation.status.success(), "{}", String::from_utf8_lossy(&compilation.stderr));
+
+  check_results(&mut Command::new(path.join("main")));
+}
+
+/// Necesita `tsc` y `node`, así que solo se ejecuta con
+/// `cargo test -- --ignored`. Sin ellos, falla.
+#[test]
+#[ignore = "necesita tsc y node"]
+fn typescript_parser() {
+  let path = directory("typescript");
+  let code = codegen(EXAMPLE, &["--target", "typescript"]).stdout;
+  fs::write(path.join("parser.ts"), code).unwrap();
+  fs::write(path.join("main.ts"), TYPESCRIPT_DRIVER).unwrap();
+
+  let compilation = Command::new("tsc")
+    .args(["--strict", "--target", "es2017", "--module", "commonjs", "--outDir", "out", "main.ts"])
+    .current_dir(&path)
+    .output()
+    .expect("no se pudo ejecutar tsc");
+  assert!(compilation.status.success(), "{}", String::from_utf8_lossy(&compilation.stdout));
+
+  check_results(Command::new("node").arg(Path::new("out").join("main.js")).current_dir(&path));
+}